@@ -2,11 +2,21 @@ pub mod confirm_history;
 #[cfg(feature = "client_diagnostics")]
 pub mod diagnostics;
 pub mod event;
+pub mod interpolation;
+pub mod pending_mapping;
+pub mod prediction;
+pub mod repair;
+pub mod replay;
+pub mod server_clock;
 pub mod server_mutate_ticks;
 
-use std::{io::Cursor, mem};
+use std::{collections::VecDeque, io::Cursor, mem, time::Duration};
 
-use bevy::{ecs::world::CommandQueue, prelude::*};
+use bevy::{
+    ecs::{component::ComponentId, entity::EntityHashSet, world::CommandQueue},
+    prelude::*,
+    utils::Instant,
+};
 use bincode::{DefaultOptions, Options};
 use bytes::Bytes;
 use integer_encoding::{FixedIntReader, VarIntReader};
@@ -19,32 +29,65 @@ use crate::core::{
         command_markers::{CommandMarkers, EntityMarkers},
         deferred_entity::DeferredEntity,
         replication_registry::{
-            ctx::{DespawnCtx, RemoveCtx, WriteCtx},
+            self,
+            ctx::{DespawnCtx, RemoveCtx, SpawnCtx, WriteCtx},
             ReplicationRegistry,
         },
+        resync_request::ResyncRequest,
         track_mutate_messages::TrackMutateMessages,
         update_message_flags::UpdateMessageFlags,
         Replicated,
     },
-    replicon_client::RepliconClient,
+    replicon_client::{DisconnectReason, RepliconClient},
     replicon_tick::RepliconTick,
     server_entity_map::ServerEntityMap,
 };
 use confirm_history::{ConfirmHistory, EntityReplicated};
+use pending_mapping::{MappingOutcome, MappingTimeout, UnmappedEntityPolicy};
 use server_mutate_ticks::{MutateTickReceived, ServerMutateTicks};
 
 /// Client functionality and replication receiving.
 ///
 /// Can be disabled for server-only apps.
-pub struct ClientPlugin;
+pub struct ClientPlugin {
+    /// Number of ticks tracked by [`ConfirmHistory`] and [`ServerMutateTicks`].
+    ///
+    /// Mutations older than this number of ticks since the last received tick for an entity
+    /// are discarded, even for markers that require history.
+    /// For [`ConfirmHistory`] the value is rounded up to a multiple of 64.
+    pub history_len: u32,
+
+    /// Time after which entities with [`PendingMapping`](pending_mapping::PendingMapping) are considered expired.
+    ///
+    /// See also [`MappingOutcome::Expired`].
+    pub mapping_timeout: Duration,
+}
+
+impl Default for ClientPlugin {
+    fn default() -> Self {
+        Self {
+            history_len: u64::BITS,
+            mapping_timeout: Duration::from_secs(5),
+        }
+    }
+}
 
 impl Plugin for ClientPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<RepliconClient>()
+        app.insert_resource(HistoryLen(self.history_len))
+            .insert_resource(MappingTimeout(self.mapping_timeout))
+            .init_resource::<RepliconClient>()
             .init_resource::<ServerEntityMap>()
             .init_resource::<ServerUpdateTick>()
             .init_resource::<BufferedMutations>()
+            .init_resource::<BufferedUpdates>()
+            .init_resource::<ReplicationBudget>()
+            .init_resource::<ReplicationPaused>()
+            .init_resource::<MalformedMessagePolicy>()
+            .init_resource::<UnmappedEntityPolicy>()
             .add_event::<EntityReplicated>()
+            .add_event::<MappingOutcome>()
+            .add_event::<ReplicationError>()
             .add_event::<MutateTickReceived>()
             .configure_sets(
                 PreUpdate,
@@ -67,16 +110,23 @@ impl Plugin for ClientPlugin {
             .add_systems(
                 PreUpdate,
                 Self::receive_replication
-                    .map(Result::unwrap)
                     .in_set(ClientSet::Receive)
                     .run_if(client_connected),
             )
-            .add_systems(PreUpdate, Self::reset.in_set(ClientSet::Reset));
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::reset.in_set(ClientSet::Reset),
+                    pending_mapping::expire
+                        .after(ClientSet::Receive)
+                        .run_if(client_connected),
+                ),
+            );
     }
 
     fn finish(&self, app: &mut App) {
         if **app.world().resource::<TrackMutateMessages>() {
-            app.init_resource::<ServerMutateTicks>();
+            app.insert_resource(ServerMutateTicks::new(self.history_len as usize));
         }
     }
 }
@@ -84,6 +134,7 @@ impl Plugin for ClientPlugin {
 impl ClientPlugin {
     fn setup_channels(mut client: ResMut<RepliconClient>, channels: Res<RepliconChannels>) {
         client.setup_server_channels(channels.server_channels().len());
+        client.setup_message_transform(channels.message_transform().cloned());
     }
 
     /// Receives and applies replication messages from the server.
@@ -101,51 +152,80 @@ impl ClientPlugin {
     ///
     /// Acknowledgments for received mutate messages are sent back to the server.
     ///
+    /// Malformed messages are reported via [`ReplicationError`] and handled according to [`MalformedMessagePolicy`].
+    ///
     /// See also [`ReplicationMessages`](crate::server::replication_messages::ReplicationMessages).
     pub(super) fn receive_replication(
         world: &mut World,
         mut queue: Local<CommandQueue>,
         mut entity_markers: Local<EntityMarkers>,
-    ) -> bincode::Result<()> {
+    ) {
         world.resource_scope(|world, mut client: Mut<RepliconClient>| {
             world.resource_scope(|world, mut entity_map: Mut<ServerEntityMap>| {
-                world.resource_scope(|world, mut buffered_mutations: Mut<BufferedMutations>| {
-                    world.resource_scope(|world, command_markers: Mut<CommandMarkers>| {
-                        world.resource_scope(|world, registry: Mut<ReplicationRegistry>| {
-                            world.resource_scope(
-                                |world, mut replicated_events: Mut<Events<EntityReplicated>>| {
-                                    let mut stats =
-                                        world.remove_resource::<ClientReplicationStats>();
-                                    let mut mutate_ticks =
-                                        world.remove_resource::<ServerMutateTicks>();
-                                    let mut params = ReceiveParams {
-                                        queue: &mut queue,
-                                        entity_markers: &mut entity_markers,
-                                        entity_map: &mut entity_map,
-                                        replicated_events: &mut replicated_events,
-                                        mutate_ticks: mutate_ticks.as_mut(),
-                                        stats: stats.as_mut(),
-                                        command_markers: &command_markers,
-                                        registry: &registry,
-                                    };
-
-                                    apply_replication(
-                                        world,
-                                        &mut params,
-                                        &mut client,
-                                        &mut buffered_mutations,
-                                    )?;
-
-                                    if let Some(stats) = stats {
-                                        world.insert_resource(stats);
-                                    }
-                                    if let Some(mutate_ticks) = mutate_ticks {
-                                        world.insert_resource(mutate_ticks);
-                                    }
-
-                                    Ok(())
-                                },
-                            )
+                world.resource_scope(|world, mut buffered_updates: Mut<BufferedUpdates>| {
+                    world.resource_scope(|world, mut buffered_mutations: Mut<BufferedMutations>| {
+                        world.resource_scope(|world, command_markers: Mut<CommandMarkers>| {
+                            world.resource_scope(|world, registry: Mut<ReplicationRegistry>| {
+                                world.resource_scope(
+                                    |world, mut replicated_events: Mut<Events<EntityReplicated>>| {
+                                        let mut stats =
+                                            world.remove_resource::<ClientReplicationStats>();
+                                        let mut mutate_ticks =
+                                            world.remove_resource::<ServerMutateTicks>();
+                                        let history_len = **world.resource::<HistoryLen>();
+                                        let mut params = ReceiveParams {
+                                            queue: &mut queue,
+                                            entity_markers: &mut entity_markers,
+                                            entity_map: &mut entity_map,
+                                            replicated_events: &mut replicated_events,
+                                            mutate_ticks: mutate_ticks.as_mut(),
+                                            stats: stats.as_mut(),
+                                            command_markers: &command_markers,
+                                            registry: &registry,
+                                            history_len,
+                                            message_entities: Default::default(),
+                                        };
+
+                                        let policy = *world.resource::<MalformedMessagePolicy>();
+                                        let applied = apply_replication(
+                                            world,
+                                            &mut params,
+                                            &mut client,
+                                            &mut buffered_updates,
+                                            &mut buffered_mutations,
+                                            policy,
+                                        );
+
+                                        if !applied {
+                                            match policy {
+                                                MalformedMessagePolicy::Skip => unreachable!(
+                                                    "processing should stop only with other policies"
+                                                ),
+                                                MalformedMessagePolicy::Reset => {
+                                                    debug!("requesting resync from the server");
+                                                    buffered_updates.clear();
+                                                    buffered_mutations.clear();
+                                                    world.send_event(ResyncRequest);
+                                                }
+                                                MalformedMessagePolicy::Disconnect => {
+                                                    debug!("disconnecting from the server");
+                                                    client.set_disconnected(
+                                                        DisconnectReason::MalformedMessage,
+                                                    );
+                                                    client.request_disconnect();
+                                                }
+                                            }
+                                        }
+
+                                        if let Some(stats) = stats {
+                                            world.insert_resource(stats);
+                                        }
+                                        if let Some(mutate_ticks) = mutate_ticks {
+                                            world.insert_resource(mutate_ticks);
+                                        }
+                                    },
+                                )
+                            })
                         })
                     })
                 })
@@ -156,11 +236,13 @@ impl ClientPlugin {
     fn reset(
         mut update_tick: ResMut<ServerUpdateTick>,
         mut entity_map: ResMut<ServerEntityMap>,
+        mut buffered_updates: ResMut<BufferedUpdates>,
         mut buffered_mutations: ResMut<BufferedMutations>,
         stats: Option<ResMut<ClientReplicationStats>>,
     ) {
         *update_tick = Default::default();
         entity_map.clear();
+        buffered_updates.clear();
         buffered_mutations.clear();
         if let Some(mut stats) = stats {
             *stats = Default::default();
@@ -168,17 +250,46 @@ impl ClientPlugin {
     }
 }
 
-/// Reads all received messages and applies them.
+/// Reads all received messages and applies them within [`ReplicationBudget`].
+///
+/// If [`ReplicationPaused`] is set, only buffers them.
 ///
 /// Sends acknowledgments for mutate messages back.
+///
+/// Returns `false` if the processing was stopped due to a malformed message.
 fn apply_replication(
     world: &mut World,
     params: &mut ReceiveParams,
     client: &mut RepliconClient,
+    buffered_updates: &mut BufferedUpdates,
     buffered_mutations: &mut BufferedMutations,
-) -> bincode::Result<()> {
-    for message in client.receive(ReplicationChannel::Updates) {
-        apply_update_message(world, params, &message)?;
+    policy: MalformedMessagePolicy,
+) -> bool {
+    let mut budget = BudgetTracker::new(*world.resource::<ReplicationBudget>());
+    let paused = **world.resource::<ReplicationPaused>();
+
+    buffered_updates
+        .0
+        .extend(client.receive(ReplicationChannel::Updates));
+    while !paused && !budget.exhausted() {
+        let Some(message) = buffered_updates.0.pop_front() else {
+            break;
+        };
+        match apply_update_message(world, params, &message) {
+            Ok(entities) => budget.consume(entities),
+            Err(error) => {
+                if !report_error(world, params, policy, error) {
+                    return false;
+                }
+            }
+        }
+    }
+    if !buffered_updates.0.is_empty() {
+        trace!(
+            "postponing {} update message(s) due to the {}",
+            buffered_updates.0.len(),
+            if paused { "pause" } else { "budget" }
+        );
     }
 
     // Unlike update messages, we read all mutate messages first, sort them by tick
@@ -190,24 +301,72 @@ fn apply_replication(
     let acks_size = mem::size_of::<u16>() * client.received_count(ReplicationChannel::Mutations);
     if acks_size != 0 {
         let mut acks = Vec::with_capacity(acks_size);
+        let mut stopped = false;
         for message in client.receive(ReplicationChannel::Mutations) {
-            let mutate_index = buffer_mutate_message(params, buffered_mutations, message)?;
-            bincode::serialize_into(&mut acks, &mutate_index)?;
+            match buffer_mutate_message(params, buffered_mutations, message) {
+                Ok(mutate_index) => bincode::serialize_into(&mut acks, &mutate_index)
+                    .expect("index should always be serializable into a vec"),
+                Err(error) => {
+                    let error = ReplicationError::new(ReplicationChannel::Mutations, None, error);
+                    if !report_error(world, params, policy, error) {
+                        stopped = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if !acks.is_empty() {
+            client.send(ReplicationChannel::Updates, acks);
         }
-        client.send(ReplicationChannel::Updates, acks);
+        if stopped {
+            return false;
+        }
+    }
+
+    if paused {
+        return true;
     }
 
-    apply_mutate_messages(world, params, buffered_mutations, update_tick)
+    apply_mutate_messages(
+        world,
+        params,
+        buffered_mutations,
+        update_tick,
+        &mut budget,
+        policy,
+    )
+}
+
+/// Reports a malformed message.
+///
+/// Returns `true` if the remaining messages should be processed.
+fn report_error(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    policy: MalformedMessagePolicy,
+    error: ReplicationError,
+) -> bool {
+    error!(
+        "received malformed message over {:?} for {:?}: {}",
+        error.channel, error.tick, error.error
+    );
+
+    discard_commands(world, params);
+    world.send_event(error);
+
+    policy == MalformedMessagePolicy::Skip
 }
 
 /// Reads and applies an update message.
 ///
 /// For details see [`replication_messages`](crate::server::replication_messages).
+///
+/// Returns the number of applied entities.
 fn apply_update_message(
     world: &mut World,
     params: &mut ReceiveParams,
     message: &[u8],
-) -> bincode::Result<()> {
+) -> Result<usize, ReplicationError> {
     let end_pos = message.len();
     let mut cursor = Cursor::new(message);
     if let Some(stats) = &mut params.stats {
@@ -215,13 +374,131 @@ fn apply_update_message(
         stats.bytes += end_pos;
     }
 
-    let flags = UpdateMessageFlags::from_bits_retain(cursor.read_fixedint()?);
-    debug_assert!(!flags.is_empty(), "message can't be empty");
+    let (flags, message_tick) = read_update_header(&mut cursor)
+        .map_err(|error| ReplicationError::new(ReplicationChannel::Updates, None, error))?;
 
-    let message_tick = bincode::deserialize_from(&mut cursor)?;
     trace!("applying update message for {message_tick:?}");
-    world.resource_mut::<ServerUpdateTick>().0 = message_tick;
+    // Could contain entities spawned by mutate messages, which aren't reverted.
+    params.message_entities.spawned.clear();
+    let last_tick = mem::replace(
+        &mut *world.resource_mut::<ServerUpdateTick>(),
+        ServerUpdateTick(message_tick),
+    );
 
+    // All entities that are missing in the resync message should be despawned.
+    let mut stale_entities = flags.contains(UpdateMessageFlags::RESYNC).then(|| {
+        debug!("applying resync for {message_tick:?}");
+        params
+            .entity_map
+            .to_client()
+            .keys()
+            .copied()
+            .collect::<EntityHashSet>()
+    });
+
+    let entities = match apply_update_data(
+        world,
+        params,
+        &mut cursor,
+        flags.data(),
+        message_tick,
+        stale_entities.as_mut(),
+    ) {
+        Ok(entities) => entities,
+        Err(error) => {
+            revert_update_message(world, params, message_tick, last_tick);
+            return Err(ReplicationError::new(
+                ReplicationChannel::Updates,
+                Some(message_tick),
+                error,
+            ));
+        }
+    };
+
+    let message_entities = mem::take(&mut params.message_entities);
+    for outcome in message_entities.outcomes {
+        pending_mapping::apply_outcome(world, outcome);
+    }
+
+    if let Some(stale_entities) = stale_entities {
+        for server_entity in stale_entities {
+            despawn_entity(world, params, server_entity, message_tick);
+        }
+    }
+
+    Ok(entities)
+}
+
+/// Reverts entities spawned or mapped by a malformed update message and restores [`ServerUpdateTick`].
+///
+/// Changes to already existing entities that were applied before the entity with the error are kept.
+fn revert_update_message(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    message_tick: RepliconTick,
+    last_tick: ServerUpdateTick,
+) {
+    discard_commands(world, params);
+    *world.resource_mut::<ServerUpdateTick>() = last_tick;
+
+    let message_entities = mem::take(&mut params.message_entities);
+    debug!(
+        "reverting {} spawned and {} mapped entities",
+        message_entities.spawned.len(),
+        message_entities.mapped.len()
+    );
+    for server_entity in message_entities.spawned {
+        despawn_entity(world, params, server_entity, message_tick);
+    }
+    for server_entity in message_entities.mapped {
+        if let Some(mut entity) = params
+            .entity_map
+            .remove_by_server(server_entity)
+            .and_then(|entity| world.get_entity_mut(entity).ok())
+        {
+            entity.remove::<Replicated>();
+        }
+    }
+}
+
+/// Drops commands queued for the entity on which the error occurred to roll back its partial state.
+fn discard_commands(world: &mut World, params: &mut ReceiveParams) {
+    *params.queue = CommandQueue::default();
+
+    // Entities reserved by the dropped commands need to be spawned to be reverted.
+    world.flush();
+}
+
+/// Reads flags and tick of an update message.
+fn read_update_header(
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<(UpdateMessageFlags, RepliconTick)> {
+    let flags = UpdateMessageFlags::from_bits_retain(cursor.read_fixedint()?);
+    let message_tick = deserialize_tick(cursor, flags.contains(UpdateMessageFlags::VARINT_TICK))?;
+    if flags.data().is_empty() && !flags.contains(UpdateMessageFlags::RESYNC) {
+        return Err(Box::new(bincode::ErrorKind::Custom(
+            "update message can't be empty".into(),
+        )));
+    }
+
+    Ok((flags, message_tick))
+}
+
+/// Applies all arrays from an update message according to `flags`.
+///
+/// If `stale_entities` is set, the message is applied as a resync.
+/// See [`apply_changes`] for details.
+///
+/// Returns the number of applied entities.
+fn apply_update_data(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    cursor: &mut Cursor<&[u8]>,
+    flags: UpdateMessageFlags,
+    message_tick: RepliconTick,
+    mut stale_entities: Option<&mut EntityHashSet>,
+) -> bincode::Result<usize> {
+    let mut entities = 0;
     let last_flag = flags.last();
     for (_, flag) in flags.iter_names() {
         let array_kind = if flag != last_flag {
@@ -230,46 +507,59 @@ fn apply_update_message(
             ArrayKind::Dynamic
         };
 
-        match flag {
+        entities += match flag {
             UpdateMessageFlags::MAPPINGS => {
                 debug_assert_eq!(array_kind, ArrayKind::Sized);
-                let len = apply_array(array_kind, &mut cursor, |cursor| {
+                let len = apply_array(array_kind, cursor, |cursor| {
                     apply_entity_mapping(world, params, cursor)
                 })?;
                 if let Some(stats) = &mut params.stats {
                     stats.mappings += len;
                 }
+                len
+            }
+            UpdateMessageFlags::UNMAPPED => {
+                apply_array(array_kind, cursor, |cursor| apply_unmapped(params, cursor))?
             }
             UpdateMessageFlags::DESPAWNS => {
-                let len = apply_array(array_kind, &mut cursor, |cursor| {
+                let len = apply_array(array_kind, cursor, |cursor| {
                     apply_despawn(world, params, cursor, message_tick)
                 })?;
                 if let Some(stats) = &mut params.stats {
                     stats.despawns += len;
                 }
+                len
             }
             UpdateMessageFlags::REMOVALS => {
-                let len = apply_array(array_kind, &mut cursor, |cursor| {
+                let len = apply_array(array_kind, cursor, |cursor| {
                     apply_removals(world, params, cursor, message_tick)
                 })?;
                 if let Some(stats) = &mut params.stats {
                     stats.entities_changed += len;
                 }
+                len
             }
             UpdateMessageFlags::CHANGES => {
                 debug_assert_eq!(array_kind, ArrayKind::Dynamic);
-                let len = apply_array(array_kind, &mut cursor, |cursor| {
-                    apply_changes(world, params, cursor, message_tick)
+                let len = apply_array(array_kind, cursor, |cursor| {
+                    apply_changes(
+                        world,
+                        params,
+                        cursor,
+                        message_tick,
+                        stale_entities.as_deref_mut(),
+                    )
                 })?;
                 if let Some(stats) = &mut params.stats {
                     stats.entities_changed += len;
                 }
+                len
             }
             _ => unreachable!("iteration should yield only named flags"),
-        }
+        };
     }
 
-    Ok(())
+    Ok(entities)
 }
 
 /// Reads and buffers mutate message.
@@ -289,8 +579,9 @@ fn buffer_mutate_message(
         stats.bytes += end_pos;
     }
 
-    let update_tick = bincode::deserialize_from(&mut cursor)?;
-    let message_tick = bincode::deserialize_from(&mut cursor)?;
+    let ticks_header: u64 = cursor.read_varint()?;
+    let update_tick = deserialize_tick(&mut cursor, ticks_header & 1 != 0)?;
+    let message_tick = update_tick + (ticks_header >> 1) as u32;
     let messages_count = if params.mutate_ticks.is_some() {
         cursor.read_varint()?
     } else {
@@ -308,19 +599,35 @@ fn buffer_mutate_message(
     Ok(mutate_index)
 }
 
+/// Deserializes a tick written as varint or fixint.
+///
+/// See [`UpdateMessageFlags::VARINT_TICK`] for details.
+fn deserialize_tick(cursor: &mut Cursor<&[u8]>, varint: bool) -> bincode::Result<RepliconTick> {
+    if varint {
+        let tick = cursor.read_varint()?;
+        Ok(RepliconTick::new(tick))
+    } else {
+        bincode::deserialize_from(cursor)
+    }
+}
+
 /// Applies mutations from [`BufferedMutations`].
 ///
 /// If the mutate message can't be applied yet (because the update message with the
-/// corresponding tick hasn't arrived), it will be kept in the buffer.
+/// corresponding tick hasn't arrived or the budget is exhausted), it will be kept in the buffer.
+///
+/// Returns `false` if the processing was stopped due to a malformed message.
 fn apply_mutate_messages(
     world: &mut World,
     params: &mut ReceiveParams,
     buffered_mutations: &mut BufferedMutations,
     update_tick: ServerUpdateTick,
-) -> bincode::Result<()> {
-    let mut result = Ok(());
+    budget: &mut BudgetTracker,
+    policy: MalformedMessagePolicy,
+) -> bool {
+    let mut stopped = false;
     buffered_mutations.0.retain(|mutate| {
-        if mutate.update_tick > *update_tick {
+        if stopped || mutate.update_tick > *update_tick || budget.exhausted() {
             return true;
         }
 
@@ -333,11 +640,24 @@ fn apply_mutate_messages(
 
         match len {
             Ok(len) => {
+                budget.consume(len);
                 if let Some(stats) = &mut params.stats {
                     stats.entities_changed += len;
                 }
             }
-            Err(e) => result = Err(e),
+            Err(error) => {
+                let error = ReplicationError::new(
+                    ReplicationChannel::Mutations,
+                    Some(mutate.message_tick),
+                    error,
+                );
+                stopped = !report_error(world, params, policy, error);
+
+                // Entities spawned during mapping lost their commands.
+                for server_entity in mem::take(&mut params.message_entities.spawned) {
+                    despawn_entity(world, params, server_entity, mutate.message_tick);
+                }
+            }
         }
 
         if let Some(mutate_ticks) = &mut params.mutate_ticks {
@@ -351,7 +671,7 @@ fn apply_mutate_messages(
         false
     });
 
-    result
+    !stopped
 }
 
 /// Deserializes and applies server mapping from client's pre-spawned entities.
@@ -364,17 +684,68 @@ fn apply_entity_mapping(
     let client_entity = entity_serde::deserialize_entity(cursor)?;
 
     if let Ok(mut entity) = world.get_entity_mut(client_entity) {
-        debug!("received mapping from {server_entity:?} to {client_entity:?}");
         entity.insert(Replicated);
         params.entity_map.insert(server_entity, client_entity);
-    } else {
-        // Entity could be despawned on client already.
-        debug!("received mapping from {server_entity:?} to {client_entity:?}, but the entity doesn't exists");
+        params.message_entities.mapped.push(server_entity);
     }
+    params
+        .message_entities
+        .outcomes
+        .push(MappingOutcome::Mapped {
+            client_entity,
+            server_entity,
+        });
+
+    Ok(())
+}
+
+/// Deserializes client's pre-spawned entity that won't be mapped.
+///
+/// [`UnmappedEntityPolicy`] will be applied to it after the message is fully read.
+fn apply_unmapped(params: &mut ReceiveParams, cursor: &mut Cursor<&[u8]>) -> bincode::Result<()> {
+    let client_entity = entity_serde::deserialize_entity(cursor)?;
+    let dropped: u8 = cursor.read_fixedint()?;
+    let outcome = if dropped != 0 {
+        MappingOutcome::Dropped { client_entity }
+    } else {
+        MappingOutcome::Rejected { client_entity }
+    };
+    params.message_entities.outcomes.push(outcome);
 
     Ok(())
 }
 
+/// Returns a client entity mapped to a server entity, spawning it with [`ReplicationRegistry::spawn`] if it's missing.
+///
+/// Spawned entities are recorded to be reverted if the message turns out to be malformed.
+fn get_or_spawn(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    server_entity: Entity,
+    message_tick: RepliconTick,
+) -> Entity {
+    if let Some(client_entity) = params.entity_map.get_by_server_or_restore(server_entity) {
+        return client_entity;
+    }
+
+    let ctx = SpawnCtx {
+        server_entity,
+        message_tick,
+    };
+    let mut commands = Commands::new(params.queue, world);
+    let client_entity = replication_registry::spawn_replicated(
+        params.registry.spawn,
+        &ctx,
+        &mut commands,
+        params.entity_map,
+    );
+    params.queue.apply(world);
+    params.entity_map.insert(server_entity, client_entity);
+    params.message_entities.spawned.push(server_entity);
+
+    client_entity
+}
+
 /// Deserializes and applies entity despawn from update message.
 fn apply_despawn(
     world: &mut World,
@@ -386,6 +757,18 @@ fn apply_despawn(
     // with the last replication message, but the server might not yet have received confirmation
     // from the client and could include the deletion in the this message.
     let server_entity = entity_serde::deserialize_entity(cursor)?;
+    despawn_entity(world, params, server_entity, message_tick);
+
+    Ok(())
+}
+
+/// Despawns a client entity mapped to a server entity using [`ReplicationRegistry::despawn`].
+fn despawn_entity(
+    world: &mut World,
+    params: &mut ReceiveParams,
+    server_entity: Entity,
+    message_tick: RepliconTick,
+) {
     if let Some(client_entity) = params
         .entity_map
         .remove_by_server(server_entity)
@@ -394,8 +777,6 @@ fn apply_despawn(
         let ctx = DespawnCtx { message_tick };
         (params.registry.despawn)(&ctx, client_entity);
     }
-
-    Ok(())
 }
 
 /// Deserializes and applies component removals for an entity.
@@ -407,9 +788,7 @@ fn apply_removals(
 ) -> bincode::Result<()> {
     let server_entity = entity_serde::deserialize_entity(cursor)?;
 
-    let client_entity = params
-        .entity_map
-        .get_by_server_or_insert(server_entity, || world.spawn(Replicated).id());
+    let client_entity = get_or_spawn(world, params, server_entity, message_tick);
 
     let mut client_entity = DeferredEntity::new(world, client_entity);
     let mut commands = client_entity.commands(params.queue);
@@ -422,11 +801,12 @@ fn apply_removals(
         &mut client_entity,
         params.replicated_events,
         message_tick,
+        params.history_len,
     );
 
     let len = apply_array(ArrayKind::Sized, cursor, |cursor| {
         let fns_id = DefaultOptions::new().deserialize_from(&mut *cursor)?;
-        let (component_id, component_fns, _) = params.registry.get(fns_id);
+        let (component_id, component_fns, _) = params.registry.try_get(fns_id)?;
         let mut ctx = RemoveCtx {
             commands: &mut commands,
             message_tick,
@@ -447,17 +827,19 @@ fn apply_removals(
 }
 
 /// Deserializes and applies component insertions and/or mutations for an entity.
+///
+/// If `stale_entities` is set, removes the entity from it and removes all replicated
+/// components that are missing in the message.
 fn apply_changes(
     world: &mut World,
     params: &mut ReceiveParams,
     cursor: &mut Cursor<&[u8]>,
     message_tick: RepliconTick,
+    stale_entities: Option<&mut EntityHashSet>,
 ) -> bincode::Result<()> {
     let server_entity = entity_serde::deserialize_entity(cursor)?;
 
-    let client_entity = params
-        .entity_map
-        .get_by_server_or_insert(server_entity, || world.spawn(Replicated).id());
+    let client_entity = get_or_spawn(world, params, server_entity, message_tick);
 
     let mut client_entity = DeferredEntity::new(world, client_entity);
     let mut commands = client_entity.commands(params.queue);
@@ -470,12 +852,24 @@ fn apply_changes(
         &mut client_entity,
         params.replicated_events,
         message_tick,
+        params.history_len,
     );
 
+    let mut written_ids = Vec::new();
     let len = apply_array(ArrayKind::Sized, cursor, |cursor| {
         let fns_id = DefaultOptions::new().deserialize_from(&mut *cursor)?;
-        let (component_id, component_fns, rule_fns) = params.registry.get(fns_id);
-        let mut ctx = WriteCtx::new(&mut commands, params.entity_map, component_id, message_tick);
+        let (component_id, component_fns, rule_fns) = params.registry.try_get(fns_id)?;
+        if stale_entities.is_some() {
+            written_ids.push(component_id);
+        }
+        let mut ctx = WriteCtx::new(
+            &mut commands,
+            params.entity_map,
+            component_id,
+            message_tick,
+            params.registry.spawn,
+            &mut params.message_entities.spawned,
+        );
 
         // SAFETY: `rule_fns` and `component_fns` were created for the same type.
         unsafe {
@@ -491,6 +885,18 @@ fn apply_changes(
         Ok(())
     })?;
 
+    if let Some(stale_entities) = stale_entities {
+        stale_entities.remove(&server_entity);
+        remove_stale_components(
+            &mut commands,
+            params.entity_markers,
+            params.registry,
+            &mut client_entity,
+            message_tick,
+            |component_id, _| !written_ids.contains(&component_id),
+        );
+    }
+
     if let Some(stats) = &mut params.stats {
         stats.components_changed += len;
     }
@@ -500,6 +906,30 @@ fn apply_changes(
     Ok(())
 }
 
+/// Removes replicated components from an entity for which `is_stale` returns `true`.
+///
+/// Used to clean up components that are no longer present on the server after a resync
+/// or a reconnect.
+pub(super) fn remove_stale_components(
+    commands: &mut Commands,
+    entity_markers: &EntityMarkers,
+    registry: &ReplicationRegistry,
+    client_entity: &mut DeferredEntity,
+    message_tick: RepliconTick,
+    mut is_stale: impl FnMut(ComponentId, &DeferredEntity) -> bool,
+) {
+    for (component_id, component_fns) in registry.iter_component_fns() {
+        if client_entity.contains_id(component_id) && is_stale(component_id, client_entity) {
+            let mut ctx = RemoveCtx {
+                commands: &mut *commands,
+                message_tick,
+                component_id,
+            };
+            component_fns.remove(&mut ctx, entity_markers, client_entity);
+        }
+    }
+}
+
 fn apply_array(
     kind: ArrayKind,
     cursor: &mut Cursor<&[u8]>,
@@ -541,13 +971,14 @@ fn confirm_tick(
     entity: &mut DeferredEntity,
     replicated_events: &mut Events<EntityReplicated>,
     tick: RepliconTick,
+    history_len: u32,
 ) {
     if let Some(mut history) = entity.get_mut::<ConfirmHistory>() {
         history.set_last_tick(tick);
     } else {
         commands
             .entity(entity.id())
-            .insert(ConfirmHistory::new(tick));
+            .insert(ConfirmHistory::with_len(tick, history_len));
     }
     replicated_events.send(EntityReplicated {
         entity: entity.id(),
@@ -574,15 +1005,20 @@ fn apply_mutations(
         return Ok(());
     };
 
+    // Mutations aren't reverted, so only entities spawned for the current entity need to be tracked.
+    params.message_entities.spawned.clear();
+
     let mut client_entity = DeferredEntity::new(world, client_entity);
     let mut commands = client_entity.commands(params.queue);
     params
         .entity_markers
         .read(params.command_markers, &*client_entity);
 
-    let mut history = client_entity
-        .get_mut::<ConfirmHistory>()
-        .expect("all entities from mutate message should have confirmed ticks");
+    let Some(mut history) = client_entity.get_mut::<ConfirmHistory>() else {
+        return Err(Box::new(bincode::ErrorKind::Custom(format!(
+            "mutated server's {server_entity:?} wasn't received in an update message"
+        ))));
+    };
     let new_tick = message_tick > history.last_tick();
     if new_tick {
         history.set_last_tick(message_tick);
@@ -597,7 +1033,7 @@ fn apply_mutations(
         }
 
         let ago = history.last_tick().get().wrapping_sub(message_tick.get());
-        if ago >= u64::BITS {
+        if ago >= history.history_len() {
             trace!(
                 "discarding {ago} ticks old mutations for client's {:?}",
                 client_entity.id()
@@ -617,8 +1053,15 @@ fn apply_mutations(
     let mut components_count = 0;
     while cursor.position() < end_pos {
         let fns_id = DefaultOptions::new().deserialize_from(&mut *cursor)?;
-        let (component_id, component_fns, rule_fns) = params.registry.get(fns_id);
-        let mut ctx = WriteCtx::new(&mut commands, params.entity_map, component_id, message_tick);
+        let (component_id, component_fns, rule_fns) = params.registry.try_get(fns_id)?;
+        let mut ctx = WriteCtx::new(
+            &mut commands,
+            params.entity_map,
+            component_id,
+            message_tick,
+            params.registry.spawn,
+            &mut params.message_entities.spawned,
+        );
 
         // SAFETY: `rule_fns` and `component_fns` were created for the same type.
         unsafe {
@@ -666,6 +1109,23 @@ struct ReceiveParams<'a> {
     stats: Option<&'a mut ClientReplicationStats>,
     command_markers: &'a CommandMarkers,
     registry: &'a ReplicationRegistry,
+    history_len: u32,
+    message_entities: MessageEntities,
+}
+
+/// Entities spawned or mapped by the currently applying update message.
+///
+/// Used to revert them if the message turns out to be malformed.
+#[derive(Default)]
+struct MessageEntities {
+    /// Server entities for which client entities were spawned.
+    spawned: Vec<Entity>,
+
+    /// Server entities that were mapped to client's pre-spawned entities.
+    mapped: Vec<Entity>,
+
+    /// Mapping outcomes that will be applied after the message is fully read.
+    outcomes: Vec<MappingOutcome>,
 }
 
 /// Set with replication and event systems related to client.
@@ -721,8 +1181,8 @@ pub enum ClientSet {
     /// Runs in [`PreUpdate`] when the client just disconnected.
     ///
     /// You may want to disable this set if you want to preserve client replication state across reconnects.
-    /// In that case, you need to manually repair the client state (or use something like
-    /// [`bevy_replicon_repair`](https://docs.rs/bevy_replicon_repair)).
+    /// In that case, you need to manually repair the client state (or use [`ClientRepairPlugin`](repair::ClientRepairPlugin),
+    /// which keeps this set enabled).
     ///
     /// If this set is disabled and you don't want to repair client state, then you need to manually clean up
     /// the client after a disconnect or when reconnecting.
@@ -738,6 +1198,154 @@ pub enum ClientSet {
 #[derive(Clone, Copy, Debug, Default, Deref, Resource)]
 pub struct ServerUpdateTick(RepliconTick);
 
+/// Limits the amount of replication applied per frame to avoid frame hitches.
+///
+/// Update messages are applied atomically, so if there are any, at least one message will be applied
+/// per frame. Messages that don't fit into the budget are buffered and applied on the next frames.
+/// Since [`ServerUpdateTick`] is updated only when an update message is applied, mutations and server events
+/// for later ticks will wait too.
+///
+/// Unlimited by default.
+#[derive(Clone, Copy, Debug, Default, Resource)]
+pub struct ReplicationBudget {
+    /// Maximum time to spend on applying replication per frame.
+    pub time: Option<Duration>,
+
+    /// Maximum number of entities to apply per frame.
+    pub entities: Option<usize>,
+}
+
+/// Pauses application of received replication messages.
+///
+/// While paused, update and mutate messages are still received and buffered in order
+/// into [`BufferedUpdates`] and [`BufferedMutations`], and mutate messages are acknowledged
+/// to the server. [`ServerUpdateTick`] doesn't advance, so server events for later ticks will wait too.
+/// Buffered messages are applied after resuming according to [`ReplicationBudget`].
+///
+/// Useful to keep gameplay systems from seeing half-loaded entities, e.g. while loading a level.
+///
+/// Not paused by default and not reset on disconnect.
+#[derive(Clone, Copy, Debug, Default, Deref, DerefMut, Resource)]
+pub struct ReplicationPaused(pub bool);
+
+/// Value of [`ClientPlugin::history_len`].
+#[derive(Resource, Deref)]
+struct HistoryLen(u32);
+
+/// Tracks [`ReplicationBudget`] usage within a single frame.
+struct BudgetTracker {
+    budget: ReplicationBudget,
+    start: Instant,
+    entities: usize,
+    messages: usize,
+}
+
+impl BudgetTracker {
+    fn new(budget: ReplicationBudget) -> Self {
+        Self {
+            budget,
+            start: Instant::now(),
+            entities: 0,
+            messages: 0,
+        }
+    }
+
+    /// Registers an applied message with the specified number of entities.
+    fn consume(&mut self, entities: usize) {
+        self.entities += entities;
+        self.messages += 1;
+    }
+
+    /// Returns `true` if no more messages should be applied in this frame.
+    ///
+    /// Always returns `false` until at least one message is applied to guarantee progress.
+    fn exhausted(&self) -> bool {
+        if self.messages == 0 {
+            return false;
+        }
+
+        self.budget
+            .entities
+            .is_some_and(|entities| self.entities >= entities)
+            || self
+                .budget
+                .time
+                .is_some_and(|time| self.start.elapsed() >= time)
+    }
+}
+
+/// Update messages that didn't fit into [`ReplicationBudget`].
+///
+/// If [`ClientSet::Reset`] is disabled, then this needs to be cleaned up manually with [`Self::clear`].
+#[derive(Default, Resource)]
+pub struct BufferedUpdates(VecDeque<Bytes>);
+
+impl BufferedUpdates {
+    /// Removes all postponed update messages.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns the number of postponed update messages.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no postponed update messages.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Action to take when a received replication message can't be deserialized.
+///
+/// In any case, a [`ReplicationError`] event is sent. Commands queued for the entity on which the error occurred
+/// are dropped. Entities spawned or mapped by a malformed update message are reverted and [`ServerUpdateTick`] is restored.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Resource)]
+pub enum MalformedMessagePolicy {
+    /// Skip the message and continue processing the remaining ones.
+    ///
+    /// Changes to existing entities that were applied before the error are kept.
+    #[default]
+    Skip,
+    /// Stop processing, clear [`BufferedUpdates`] and [`BufferedMutations`] and send a [`ResyncRequest`].
+    ///
+    /// The server will resend the whole replicated state, which replaces the client state
+    /// for all mapped entities, so no duplicates are spawned.
+    Reset,
+    /// Stop processing and mark [`RepliconClient`] as disconnected with [`DisconnectReason::MalformedMessage`].
+    ///
+    /// Also requests the messaging backend to close the actual connection via [`RepliconClient::request_disconnect`].
+    Disconnect,
+}
+
+/// An event that indicates that a received replication message is malformed.
+///
+/// Handled according to [`MalformedMessagePolicy`].
+#[derive(Debug, Event)]
+pub struct ReplicationError {
+    /// Channel over which the message was received.
+    pub channel: ReplicationChannel,
+
+    /// Tick of the message.
+    ///
+    /// Will be [`None`] if the message header itself is malformed.
+    pub tick: Option<RepliconTick>,
+
+    /// Deserialization error.
+    pub error: bincode::Error,
+}
+
+impl ReplicationError {
+    fn new(channel: ReplicationChannel, tick: Option<RepliconTick>, error: bincode::Error) -> Self {
+        Self {
+            channel,
+            tick,
+            error,
+        }
+    }
+}
+
 /// Cached buffered mutate messages, used to synchronize mutations with update messages.
 ///
 /// If [`ClientSet::Reset`] is disabled, then this needs to be cleaned up manually with [`Self::clear`].
@@ -745,6 +1353,7 @@ pub struct ServerUpdateTick(RepliconTick);
 pub struct BufferedMutations(Vec<BufferedMutate>);
 
 impl BufferedMutations {
+    /// Removes all buffered mutate messages.
     pub fn clear(&mut self) {
         self.0.clear();
     }
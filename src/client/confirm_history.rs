@@ -7,13 +7,14 @@ use crate::core::replicon_tick::RepliconTick;
 /// Received ticks from the server for an entity.
 ///
 /// For efficiency we store only the last received tick and
-/// a bitmask indicating whether the most recent 64 ticks were received.
+/// a bitmask indicating whether the most recent ticks were received.
+/// By default the mask covers 64 ticks, but it can be extended via [`ClientPlugin::history_len`](super::ClientPlugin::history_len).
 ///
 /// See also [`EntityReplicated`].
 #[derive(Component)]
 pub struct ConfirmHistory {
     /// Previously confirmed ticks, including the last tick at position 0.
-    mask: u64,
+    mask: TickMask,
 
     /// The last received server tick for an entity.
     last_tick: RepliconTick,
@@ -21,14 +22,23 @@ pub struct ConfirmHistory {
 
 impl Debug for ConfirmHistory {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "ConfirmHistory [{:?} {:b}]", self.last_tick, self.mask)
+        write!(f, "ConfirmHistory [{:?} {:?}]", self.last_tick, self.mask)
     }
 }
 
 impl ConfirmHistory {
-    /// Creates a new instance with a single confirmed tick.
+    /// Creates a new instance with a single confirmed tick and the default length of 64 ticks.
     pub fn new(last_tick: RepliconTick) -> Self {
-        Self { mask: 1, last_tick }
+        Self::with_len(last_tick, u64::BITS)
+    }
+
+    /// Creates a new instance with a single confirmed tick that tracks at least `len` ticks.
+    ///
+    /// The length is rounded up to a multiple of 64.
+    pub fn with_len(last_tick: RepliconTick, len: u32) -> Self {
+        let mut mask = TickMask::new(len);
+        mask.set(0);
+        Self { mask, last_tick }
     }
 
     /// Returns the last received tick for an entity.
@@ -36,27 +46,32 @@ impl ConfirmHistory {
         self.last_tick
     }
 
-    /// Returns a mask that represents the received ticks.
+    /// Returns the number of tracked ticks.
+    pub fn history_len(&self) -> u32 {
+        self.mask.len()
+    }
+
+    /// Returns a mask that represents the most recent 64 received ticks.
     pub fn mask(&self) -> u64 {
-        self.mask
+        self.mask.first
     }
 
     /// Returns `true` if this tick is confirmed for an entity.
     ///
-    /// All ticks older then 64 ticks since [`Self::last_tick`] are considered received.
+    /// All ticks older then [`Self::history_len`] ticks since [`Self::last_tick`] are considered received.
     pub fn contains(&self, tick: RepliconTick) -> bool {
         if tick > self.last_tick {
             return false;
         }
 
         let ago = self.last_tick - tick;
-        ago >= u64::BITS || (self.mask >> ago & 1) == 1
+        ago >= self.history_len() || self.mask.get(ago)
     }
 
     /// Returns `true` if any tick in the given range was confirmed for the entity with
     /// this component.
     ///
-    /// All ticks older then 64 ticks since [`Self::last_tick`] are considered received.
+    /// All ticks older then [`Self::history_len`] ticks since [`Self::last_tick`] are considered received.
     ///
     /// # Panics
     ///
@@ -68,7 +83,7 @@ impl ConfirmHistory {
         if start_tick > self.last_tick {
             return false;
         }
-        if start_tick <= self.last_tick - u64::BITS {
+        if start_tick <= self.last_tick - self.history_len() {
             return true;
         }
 
@@ -78,12 +93,10 @@ impl ConfirmHistory {
             self.last_tick
         };
 
-        let len = end_tick - start_tick + 1; // +1 because the range is inclusive.
-        let range = (1 << len) - 1; // Shift 1 to `len` and then decrement to get `len` of 1's.
-        let offset = self.last_tick - end_tick;
-        let mask = range << offset;
-
-        self.mask & mask != 0
+        // Ticks in the mask are stored in decreasing order.
+        let start = self.last_tick - end_tick;
+        let end = self.last_tick - start_tick;
+        self.mask.any(start, end)
     }
 
     /// Confirms a tick.
@@ -94,7 +107,7 @@ impl ConfirmHistory {
             self.set_last_tick(tick);
         } else {
             let ago = self.last_tick - tick;
-            if ago < u64::BITS {
+            if ago < self.history_len() {
                 self.set(ago);
             }
         }
@@ -105,10 +118,10 @@ impl ConfirmHistory {
     /// # Panics
     ///
     /// Panics if `debug_assertions` are enabled and
-    /// `ago` is bigger then [`u64::BITS`].
+    /// `ago` is bigger then [`Self::history_len`].
     pub(super) fn set(&mut self, ago: u32) {
-        debug_assert!(ago < u64::BITS);
-        self.mask |= 1 << ago;
+        debug_assert!(ago < self.history_len());
+        self.mask.set(ago);
     }
 
     /// Sets the last received tick and shifts the mask.
@@ -120,9 +133,109 @@ impl ConfirmHistory {
     pub(super) fn set_last_tick(&mut self, tick: RepliconTick) {
         debug_assert!(tick >= self.last_tick);
         let diff = tick - self.last_tick;
-        self.mask = self.mask.wrapping_shl(diff);
+        self.mask.shift(diff);
         self.last_tick = tick;
-        self.mask |= 1;
+        self.mask.set(0);
+    }
+}
+
+/// Bitset with a length that is a multiple of 64.
+///
+/// The first 64 bits are stored inline to avoid allocations for the default length.
+struct TickMask {
+    first: u64,
+    rest: Box<[u64]>,
+}
+
+impl TickMask {
+    fn new(len: u32) -> Self {
+        let words = len.div_ceil(u64::BITS).max(1) as usize;
+        Self {
+            first: 0,
+            rest: vec![0; words - 1].into(),
+        }
+    }
+
+    fn len(&self) -> u32 {
+        self.words() as u32 * u64::BITS
+    }
+
+    fn words(&self) -> usize {
+        self.rest.len() + 1
+    }
+
+    fn word(&self, index: usize) -> u64 {
+        if index == 0 {
+            self.first
+        } else {
+            self.rest[index - 1]
+        }
+    }
+
+    fn word_mut(&mut self, index: usize) -> &mut u64 {
+        if index == 0 {
+            &mut self.first
+        } else {
+            &mut self.rest[index - 1]
+        }
+    }
+
+    fn get(&self, bit: u32) -> bool {
+        let word = self.word((bit / u64::BITS) as usize);
+        (word >> (bit % u64::BITS) & 1) == 1
+    }
+
+    fn set(&mut self, bit: u32) {
+        *self.word_mut((bit / u64::BITS) as usize) |= 1 << (bit % u64::BITS);
+    }
+
+    /// Returns `true` if any bit in the inclusive range is set.
+    fn any(&self, start: u32, end: u32) -> bool {
+        let start_word = (start / u64::BITS) as usize;
+        let end_word = (end / u64::BITS) as usize;
+        (start_word..=end_word).any(|index| {
+            let low = if index == start_word {
+                start % u64::BITS
+            } else {
+                0
+            };
+            let high = if index == end_word {
+                end % u64::BITS
+            } else {
+                u64::BITS - 1
+            };
+            let range = u64::MAX >> (u64::BITS - 1 - (high - low)) << low;
+            self.word(index) & range != 0
+        })
+    }
+
+    /// Shifts all bits towards the end, discarding the ones that don't fit.
+    fn shift(&mut self, bits: u32) {
+        let word_shift = (bits / u64::BITS) as usize;
+        let bit_shift = bits % u64::BITS;
+        for index in (0..self.words()).rev() {
+            let value = match index.checked_sub(word_shift) {
+                Some(source) => {
+                    let mut value = self.word(source) << bit_shift;
+                    if bit_shift != 0 && source != 0 {
+                        value |= self.word(source - 1) >> (u64::BITS - bit_shift);
+                    }
+                    value
+                }
+                None => 0,
+            };
+            *self.word_mut(index) = value;
+        }
+    }
+}
+
+impl Debug for TickMask {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", self.first)?;
+        for word in &self.rest {
+            write!(f, " {word:b}")?;
+        }
+        Ok(())
     }
 }
 
@@ -274,6 +387,34 @@ mod tests {
         assert!(!history.contains(RepliconTick::new(u64::BITS + 2)));
     }
 
+    #[test]
+    fn long_history() {
+        let mut history = ConfirmHistory::with_len(RepliconTick::new(1), 100);
+        assert_eq!(history.history_len(), 128);
+
+        history.confirm(RepliconTick::new(u64::BITS + 2));
+        history.confirm(RepliconTick::new(u64::BITS));
+        assert_eq!(history.mask(), 0b101);
+
+        assert!(!history.contains(RepliconTick::new(0)));
+        assert!(history.contains(RepliconTick::new(1)));
+        assert!(!history.contains(RepliconTick::new(2)));
+        assert!(history.contains(RepliconTick::new(u64::BITS)));
+        assert!(history.contains(RepliconTick::new(u64::BITS + 2)));
+
+        assert!(history.contains_any(RepliconTick::new(0), RepliconTick::new(1)));
+        assert!(!history.contains_any(RepliconTick::new(2), RepliconTick::new(u64::BITS - 1)));
+        assert!(history.contains_any(RepliconTick::new(2), RepliconTick::new(u64::BITS)));
+
+        history.confirm(RepliconTick::new(200));
+        assert!(
+            history.contains(RepliconTick::new(1)),
+            "should be outside the history"
+        );
+        assert!(!history.contains(RepliconTick::new(100)));
+        assert!(history.contains(RepliconTick::new(200)));
+    }
+
     #[test]
     fn confirm_with_overflow() {
         let mut history = ConfirmHistory::new(RepliconTick::new(u32::MAX));
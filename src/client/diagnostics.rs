@@ -115,23 +115,28 @@ impl ClientDiagnosticsPlugin {
         diagnostics.add_measurement(&Self::SENT_BPS, || client.sent_bps());
         diagnostics.add_measurement(&Self::RECEIVED_BPS, || client.received_bps());
 
+        // Stats are reset on disconnect, so they can be lower than the last values after a reconnect.
         diagnostics.add_measurement(&Self::ENTITIES_CHANGED, || {
-            (stats.entities_changed - last_stats.entities_changed) as f64
+            stats
+                .entities_changed
+                .saturating_sub(last_stats.entities_changed) as f64
         });
         diagnostics.add_measurement(&Self::COMPONENTS_CHANGED, || {
-            (stats.components_changed - last_stats.components_changed) as f64
+            stats
+                .components_changed
+                .saturating_sub(last_stats.components_changed) as f64
         });
         diagnostics.add_measurement(&Self::MAPPINGS, || {
-            (stats.mappings - last_stats.mappings) as f64
+            stats.mappings.saturating_sub(last_stats.mappings) as f64
         });
         diagnostics.add_measurement(&Self::DESPAWNS, || {
-            (stats.despawns - last_stats.despawns) as f64
+            stats.despawns.saturating_sub(last_stats.despawns) as f64
         });
         diagnostics.add_measurement(&Self::REPLICATION_MESSAGES, || {
-            (stats.messages - last_stats.messages) as f64
+            stats.messages.saturating_sub(last_stats.messages) as f64
         });
         diagnostics.add_measurement(&Self::REPLICATION_BYTES, || {
-            (stats.bytes - last_stats.bytes) as f64
+            stats.bytes.saturating_sub(last_stats.bytes) as f64
         });
         *last_stats = *stats;
     }
@@ -0,0 +1,309 @@
+use std::{collections::VecDeque, io::Cursor, time::Duration};
+
+use bevy::prelude::*;
+
+use super::{
+    confirm_history::EntityReplicated, server_clock::ServerClock, ClientSet, ServerUpdateTick,
+};
+use crate::core::{
+    common_conditions::client_connected,
+    replication::{
+        command_markers::{AppMarkerExt, MarkerConfig},
+        deferred_entity::DeferredEntity,
+        replication_registry::{
+            ctx::{RemoveCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+    },
+    replicon_tick::RepliconTick,
+};
+
+/// Snapshot interpolation for entities marked with [`Interpolated`].
+///
+/// Received values of components registered via [`AppInterpolationExt::interpolate`]
+/// are buffered into [`InterpolationBuffer`] with their tick instead of being written directly.
+/// Each frame the component is set to a value interpolated between two buffered snapshots
+/// at [`InterpolationTime`], which follows the latest received tick with [`Self::delay`].
+/// All received ticks are considered, including ticks of entities that aren't interpolated.
+///
+/// Mutations that arrive late are still inserted into the buffer to fill gaps.
+///
+/// Should be added after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct InterpolationPlugin {
+    /// Number of ticks to stay behind the latest received tick.
+    ///
+    /// Larger values tolerate more jitter and packet loss at the cost of latency.
+    pub delay: u32,
+
+    /// Duration of a single server tick.
+    ///
+    /// Used to advance [`InterpolationTime`].
+    /// Ignored if [`ServerClockPlugin`](super::server_clock::ServerClockPlugin) is added,
+    /// its tick duration is used instead.
+    pub tick_duration: Duration,
+}
+
+impl Default for InterpolationPlugin {
+    fn default() -> Self {
+        Self {
+            delay: 2,
+            tick_duration: Duration::from_secs_f64(1.0 / 64.0),
+        }
+    }
+}
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InterpolationTime {
+            delay: self.delay,
+            tick_duration: self.tick_duration,
+            latest_tick: None,
+            tick: Default::default(),
+            overstep: 0.0,
+        })
+        .register_marker_with::<Interpolated>(MarkerConfig {
+            need_history: true,
+            ..Default::default()
+        })
+        .configure_sets(
+            PreUpdate,
+            InterpolationSet
+                .after(ClientSet::Receive)
+                .before(ClientSet::Diagnostics),
+        )
+        .add_systems(
+            PreUpdate,
+            (
+                Self::receive_ticks.run_if(client_connected),
+                Self::advance_time,
+            )
+                .chain()
+                .after(ClientSet::Receive)
+                .before(InterpolationSet),
+        )
+        .add_systems(PreUpdate, Self::reset.in_set(ClientSet::Reset));
+    }
+}
+
+impl InterpolationPlugin {
+    fn receive_ticks(
+        mut interpolation_time: ResMut<InterpolationTime>,
+        mut replicated_events: EventReader<EntityReplicated>,
+        update_tick: Res<ServerUpdateTick>,
+    ) {
+        // Default tick is set on reset and never sent by the server.
+        let update_tick = (update_tick.is_changed() && **update_tick != RepliconTick::default())
+            .then_some(**update_tick);
+        for tick in replicated_events
+            .read()
+            .map(|event| event.tick)
+            .chain(update_tick)
+        {
+            interpolation_time.receive(tick);
+        }
+    }
+
+    fn advance_time(
+        mut interpolation_time: ResMut<InterpolationTime>,
+        time: Res<Time>,
+        clock: Option<Res<ServerClock>>,
+    ) {
+        let tick_duration = clock.map_or(interpolation_time.tick_duration, |clock| {
+            clock.tick_duration()
+        });
+        interpolation_time.advance(time.delta(), tick_duration);
+    }
+
+    fn reset(mut interpolation_time: ResMut<InterpolationTime>) {
+        interpolation_time.latest_tick = None;
+        interpolation_time.tick = Default::default();
+        interpolation_time.overstep = 0.0;
+    }
+}
+
+/// Interpolation functions for [`App`].
+pub trait AppInterpolationExt {
+    /// Enables interpolation for component `C` on entities marked with [`Interpolated`].
+    ///
+    /// `lerp` should return a value between two snapshots at the specified fraction in range `0..=1`.
+    ///
+    /// Component should be registered for replication separately.
+    /// Requires [`InterpolationPlugin`].
+    fn interpolate<C: Component + Clone>(&mut self, lerp: LerpFn<C>) -> &mut Self;
+}
+
+impl AppInterpolationExt for App {
+    fn interpolate<C: Component + Clone>(&mut self, lerp: LerpFn<C>) -> &mut Self {
+        self.set_marker_fns::<Interpolated, C>(write_interpolated::<C>, remove_interpolated::<C>)
+            .add_systems(
+                PreUpdate,
+                interpolate_system::<C>(lerp).in_set(InterpolationSet),
+            )
+    }
+}
+
+/// Signature of component interpolation function.
+pub type LerpFn<C> = fn(&C, &C, f32) -> C;
+
+/// Marks entity as interpolated.
+///
+/// Registered components will be buffered into [`InterpolationBuffer`] and smoothed
+/// over time. See [`InterpolationPlugin`] for details.
+///
+/// Should be present before the entity receives its first update, so it's usually inserted on
+/// pre-spawned entities or in [`ClientSet::Receive`] using the blueprint pattern.
+#[derive(Component, Default, Clone, Copy)]
+pub struct Interpolated;
+
+/// Set with interpolation systems.
+///
+/// Runs in [`PreUpdate`] after [`ClientSet::Receive`].
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct InterpolationSet;
+
+/// Tick at which interpolated components are displayed.
+///
+/// Advanced each frame by [`Time::delta`] and stays behind the latest received tick
+/// by [`InterpolationPlugin::delay`]. Snaps to it if the drift exceeds twice the delay,
+/// but not less than 4 ticks.
+///
+/// Reset on disconnect.
+#[derive(Resource)]
+pub struct InterpolationTime {
+    delay: u32,
+    tick_duration: Duration,
+    latest_tick: Option<RepliconTick>,
+    tick: RepliconTick,
+    overstep: f32,
+}
+
+impl InterpolationTime {
+    /// Returns the current interpolation tick.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns the fraction of the current tick that has elapsed.
+    pub fn overstep(&self) -> f32 {
+        self.overstep
+    }
+
+    fn receive(&mut self, tick: RepliconTick) {
+        match self.latest_tick {
+            Some(latest_tick) if tick <= latest_tick => (),
+            Some(_) => self.latest_tick = Some(tick),
+            None => {
+                self.latest_tick = Some(tick);
+                self.tick = tick - self.delay;
+                self.overstep = 0.0;
+            }
+        }
+    }
+
+    fn advance(&mut self, delta: Duration, tick_duration: Duration) {
+        let Some(latest_tick) = self.latest_tick else {
+            return;
+        };
+
+        self.overstep += delta.as_secs_f32() / tick_duration.as_secs_f32();
+        let ticks = self.overstep.trunc();
+        self.tick += ticks as u32;
+        self.overstep -= ticks;
+
+        let target = latest_tick - self.delay;
+        let drift = (target - self.tick) as i32;
+        if drift.unsigned_abs() > self.snap_threshold() {
+            trace!("snapping interpolation from {:?} to {target:?}", self.tick);
+            self.tick = target;
+            self.overstep = 0.0;
+        }
+    }
+
+    /// Returns the drift in ticks after which the interpolation tick snaps to the target.
+    fn snap_threshold(&self) -> u32 {
+        (self.delay * 2).max(MIN_SNAP_THRESHOLD)
+    }
+}
+
+/// Minimum value for [`InterpolationTime::snap_threshold`].
+const MIN_SNAP_THRESHOLD: u32 = 4;
+
+/// Received values of component `C` for interpolated entities, sorted by tick.
+///
+/// Inserted automatically when the first value is received.
+#[derive(Component, Deref)]
+pub struct InterpolationBuffer<C>(VecDeque<(RepliconTick, C)>);
+
+impl<C> InterpolationBuffer<C> {
+    /// Inserts value keeping the buffer sorted.
+    ///
+    /// Replaces the value if the tick is already present.
+    fn insert(&mut self, tick: RepliconTick, value: C) {
+        let index = self.partition_point(|&(value_tick, _)| value_tick < tick);
+        match self.0.get_mut(index) {
+            Some(entry) if entry.0 == tick => entry.1 = value,
+            _ => self.0.insert(index, (tick, value)),
+        }
+    }
+}
+
+/// Creates a system that sets component `C` to interpolated value at [`InterpolationTime`].
+fn interpolate_system<C: Component + Clone>(
+    lerp: LerpFn<C>,
+) -> impl FnMut(Res<InterpolationTime>, Query<(&mut C, &mut InterpolationBuffer<C>), With<Interpolated>>)
+{
+    move |time, mut components| {
+        if time.latest_tick.is_none() {
+            return;
+        }
+
+        for (mut component, mut buffer) in &mut components {
+            // Keep only one snapshot before the current tick.
+            let before = buffer.partition_point(|&(tick, _)| tick <= time.tick);
+            if before > 1 {
+                buffer.0.drain(..before - 1);
+            }
+
+            match (buffer.front(), buffer.get(1)) {
+                (Some((from_tick, from)), Some((to_tick, to))) if *from_tick <= time.tick => {
+                    let elapsed = (time.tick - *from_tick) as f32 + time.overstep;
+                    let fraction = elapsed / (*to_tick - *from_tick) as f32;
+                    *component = (lerp)(from, to, fraction.min(1.0));
+                }
+                (Some((from_tick, from)), None) if *from_tick <= time.tick => {
+                    *component = from.clone();
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Buffers received value instead of writing it into the component.
+fn write_interpolated<C: Component + Clone>(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<C>,
+    entity: &mut DeferredEntity,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let component: C = rule_fns.deserialize(ctx, cursor)?;
+    let tick = ctx.message_tick;
+
+    if let Some(mut buffer) = entity.get_mut::<InterpolationBuffer<C>>() {
+        buffer.insert(tick, component);
+    } else {
+        ctx.commands.entity(entity.id()).insert((
+            component.clone(),
+            InterpolationBuffer([(tick, component)].into()),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Removes component `C` and its buffer.
+fn remove_interpolated<C: Component>(ctx: &mut RemoveCtx, entity: &mut DeferredEntity) {
+    ctx.commands
+        .entity(entity.id())
+        .remove::<(C, InterpolationBuffer<C>)>();
+}
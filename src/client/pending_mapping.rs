@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// Marks a client's pre-spawned entity that waits for a mapping from the server.
+///
+/// Removed when a [`MappingOutcome`] is received for the entity.
+/// If no outcome is received within [`ClientPlugin::mapping_timeout`](super::ClientPlugin::mapping_timeout),
+/// [`MappingOutcome::Expired`] will be sent and the entity will be handled according to [`UnmappedEntityPolicy`].
+///
+/// Insert it on entities that you send to the server for mapping via
+/// [`ClientEntityMap`](crate::server::client_entity_map::ClientEntityMap).
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct PendingMapping {
+    elapsed: Duration,
+}
+
+impl PendingMapping {
+    /// Returns the time passed since the entity started waiting for the mapping.
+    ///
+    /// Updated only while the client is connected.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+/// An event that indicates the result of mapping for a client's pre-spawned entity.
+///
+/// Sent for entities from [`ClientEntityMap`](crate::server::client_entity_map::ClientEntityMap)
+/// and for expired [`PendingMapping`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MappingOutcome {
+    /// The entity was mapped to a server entity.
+    Mapped {
+        client_entity: Entity,
+        server_entity: Entity,
+    },
+    /// The server explicitly rejected the entity.
+    ///
+    /// See also [`ClientEntityMap::reject`](crate::server::client_entity_map::ClientEntityMap::reject).
+    Rejected { client_entity: Entity },
+    /// The server couldn't apply the mapping because the server entity
+    /// is not visible, isn't replicated, or was already replicated.
+    Dropped { client_entity: Entity },
+    /// No outcome was received from the server within
+    /// [`ClientPlugin::mapping_timeout`](super::ClientPlugin::mapping_timeout).
+    Expired { client_entity: Entity },
+}
+
+impl MappingOutcome {
+    /// Returns the client entity for which the outcome was received.
+    pub fn client_entity(&self) -> Entity {
+        match *self {
+            MappingOutcome::Mapped { client_entity, .. }
+            | MappingOutcome::Rejected { client_entity }
+            | MappingOutcome::Dropped { client_entity }
+            | MappingOutcome::Expired { client_entity } => client_entity,
+        }
+    }
+}
+
+/// Defines how client's pre-spawned entities are handled when they won't be mapped.
+///
+/// Applied to entities with [`PendingMapping`] that received [`MappingOutcome::Rejected`],
+/// [`MappingOutcome::Dropped`], or [`MappingOutcome::Expired`] outcomes.
+/// Entities without [`PendingMapping`] are kept.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnmappedEntityPolicy {
+    /// Despawn the entity.
+    #[default]
+    Despawn,
+    /// Keep the entity and only remove [`PendingMapping`].
+    ///
+    /// Useful if you want to handle [`MappingOutcome`] manually.
+    Keep,
+}
+
+/// Timeout for [`PendingMapping`].
+///
+/// See also [`ClientPlugin::mapping_timeout`](super::ClientPlugin::mapping_timeout).
+#[derive(Resource, Deref, Clone, Copy)]
+pub(super) struct MappingTimeout(pub(super) Duration);
+
+/// Updates [`PendingMapping`] and expires entities that didn't receive a mapping in time.
+pub(super) fn expire(
+    mut commands: Commands,
+    mut outcomes: EventWriter<MappingOutcome>,
+    mut pending: Query<(Entity, &mut PendingMapping)>,
+    time: Res<Time>,
+    timeout: Res<MappingTimeout>,
+    policy: Res<UnmappedEntityPolicy>,
+) {
+    for (client_entity, mut pending_mapping) in &mut pending {
+        pending_mapping.elapsed += time.delta();
+        if pending_mapping.elapsed < **timeout {
+            continue;
+        }
+
+        debug!("mapping for {client_entity:?} expired");
+        outcomes.send(MappingOutcome::Expired { client_entity });
+        match *policy {
+            UnmappedEntityPolicy::Despawn => commands.entity(client_entity).despawn_recursive(),
+            UnmappedEntityPolicy::Keep => {
+                commands.entity(client_entity).remove::<PendingMapping>();
+            }
+        }
+    }
+}
+
+/// Handles a received outcome for a client entity according to the policy.
+///
+/// The policy is applied only if the entity has [`PendingMapping`].
+/// Does nothing if the entity doesn't exist.
+pub(super) fn apply_outcome(world: &mut World, outcome: MappingOutcome) {
+    let policy = *world.resource::<UnmappedEntityPolicy>();
+    let client_entity = outcome.client_entity();
+    let Ok(mut entity) = world.get_entity_mut(client_entity) else {
+        // Entity could be despawned on client already.
+        debug!("received {outcome:?}, but the entity doesn't exist");
+        return;
+    };
+
+    debug!("received {outcome:?}");
+    let pending = entity.take::<PendingMapping>().is_some();
+    if pending
+        && !matches!(outcome, MappingOutcome::Mapped { .. })
+        && policy == UnmappedEntityPolicy::Despawn
+    {
+        entity.despawn_recursive();
+    }
+    world.send_event(outcome);
+}
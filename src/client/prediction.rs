@@ -0,0 +1,456 @@
+use std::{collections::VecDeque, io::Cursor};
+
+use bevy::{
+    ecs::{
+        entity::EntityHashSet,
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+    },
+    prelude::*,
+};
+
+use super::{server_clock::ClientTick, ClientSet};
+use crate::core::{
+    replication::{
+        command_markers::{AppMarkerExt, MarkerConfig},
+        deferred_entity::DeferredEntity,
+        replication_registry::{
+            ctx::{RemoveCtx, WriteCtx},
+            rule_fns::RuleFns,
+        },
+    },
+    replicon_tick::RepliconTick,
+};
+
+/// Client-side prediction with rollback for entities marked with [`Predicted`].
+///
+/// Each run of [`Self::schedule`] simulates [`ClientTick`]. After the simulation,
+/// values of components registered via [`AppPredictionExt::predict`] are recorded into [`PredictionHistory`].
+/// When an authoritative value arrives from the server, it's compared with the recorded value for its tick.
+/// On a mismatch, all predicted components are restored to their state at this tick,
+/// the mismatched value is replaced with the received one and the schedule is re-run for all ticks after it.
+/// During re-simulation [`Time`] is set to [`Time<Fixed>`], just like inside [`FixedUpdate`].
+///
+/// The first authoritative value for an entity is inserted as confirmed without rollback.
+/// Entities without a recorded value at or before the rollback tick are neither restored nor re-simulated:
+/// they are stored in [`RollbackSkipped`] during re-simulation, so simulation systems should ignore them.
+/// If the mismatch is older than [`Self::history_len`] ticks, the rollback is skipped and
+/// the mismatched components are set to the received values.
+///
+/// Requires [`ServerClockPlugin`](super::server_clock::ServerClockPlugin) with
+/// [`ServerClockPlugin::fixed_lead`](super::server_clock::ServerClockPlugin::fixed_lead) to keep
+/// [`ClientTick`] ahead of the server. The server is expected to increment
+/// [`ServerTick`](crate::server::server_tick::ServerTick) at the same rate, so you will likely need
+/// [`TickPolicy::Manual`](crate::server::TickPolicy::Manual) on the server.
+///
+/// Should be added after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct PredictionPlugin {
+    /// Schedule that simulates a single tick.
+    ///
+    /// Will be re-run for each tick after the mismatched one on rollback.
+    /// Only this schedule is re-run, so it should contain only systems that simulate predicted entities.
+    ///
+    /// By default set to [`PredictionSchedule`], which runs inside [`FixedUpdate`].
+    /// A custom schedule should be run manually.
+    pub schedule: InternedScheduleLabel,
+
+    /// Maximum number of recorded values per component.
+    ///
+    /// Oldest values will be discarded first. Rollback to a tick that is more
+    /// than this number of ticks behind [`ClientTick`] will be skipped.
+    pub history_len: usize,
+}
+
+impl Default for PredictionPlugin {
+    fn default() -> Self {
+        Self {
+            schedule: PredictionSchedule.intern(),
+            history_len: 64,
+        }
+    }
+}
+
+impl Plugin for PredictionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PredictionTick>()
+            .init_resource::<RollbackTick>()
+            .init_resource::<Resimulating>()
+            .init_resource::<RollbackSkipped>()
+            .insert_resource(PredictionRegistry {
+                schedule: self.schedule,
+                history_len: self.history_len,
+                rollback_fns: Default::default(),
+            })
+            .register_marker_with::<Predicted>(MarkerConfig {
+                need_history: true,
+                ..Default::default()
+            })
+            .configure_sets(
+                self.schedule,
+                (
+                    PredictionSet::Tick,
+                    PredictionSet::Simulate,
+                    PredictionSet::Record,
+                )
+                    .chain(),
+            )
+            .add_systems(self.schedule, Self::update_tick.in_set(PredictionSet::Tick))
+            .add_systems(
+                PreUpdate,
+                Self::rollback
+                    .after(ClientSet::Receive)
+                    .before(ClientSet::Diagnostics),
+            )
+            .add_systems(PreUpdate, Self::reset.in_set(ClientSet::Reset));
+
+        if self.schedule == PredictionSchedule.intern() {
+            app.add_systems(FixedUpdate, Self::simulate);
+        }
+    }
+}
+
+impl PredictionPlugin {
+    fn simulate(world: &mut World) {
+        world.run_schedule(PredictionSchedule);
+    }
+
+    fn update_tick(
+        mut tick: ResMut<PredictionTick>,
+        client_tick: Res<ClientTick>,
+        resimulating: Res<Resimulating>,
+    ) {
+        if **resimulating {
+            tick.0 += 1;
+        } else {
+            tick.0 = **client_tick;
+        }
+        trace!("simulating {tick:?}");
+    }
+
+    fn rollback(world: &mut World) {
+        let Some(tick) = world.resource_mut::<RollbackTick>().0.take() else {
+            return;
+        };
+
+        // Copy to release the borrow, simulation systems need access to the registry.
+        let registry = world.resource::<PredictionRegistry>();
+        let schedule = registry.schedule;
+        let history_len = registry.history_len;
+        let rollback_fns = registry.rollback_fns.clone();
+
+        let last_tick = **world.resource::<ClientTick>();
+        let ticks = if tick >= last_tick {
+            0
+        } else {
+            last_tick - tick
+        };
+        if ticks as usize > history_len {
+            debug!("skipping rollback to {tick:?} that is {ticks} ticks behind {last_tick:?}");
+            for fns in rollback_fns {
+                (fns.snap)(world, tick);
+            }
+            return;
+        }
+
+        let mut skipped = EntityHashSet::default();
+        for fns in &rollback_fns {
+            (fns.collect_unrecorded)(world, tick, &mut skipped);
+        }
+        for fns in &rollback_fns {
+            (fns.restore)(world, tick, &skipped);
+        }
+        world.resource_mut::<PredictionTick>().0 = tick;
+        if ticks == 0 {
+            debug!("restored state for {tick:?} without simulation");
+            return;
+        }
+
+        debug!(
+            "rolling back {ticks} tick(s) to {tick:?}, skipping {} entities without recorded values",
+            skipped.len()
+        );
+        world.resource_mut::<RollbackSkipped>().0 = skipped;
+
+        // Simulation systems expect fixed time, like inside `FixedMain`.
+        let time = *world.resource::<Time>();
+        if let Some(fixed_time) = world.get_resource::<Time<Fixed>>() {
+            *world.resource_mut::<Time>() = fixed_time.as_generic();
+        }
+        world.resource_mut::<Resimulating>().0 = true;
+        for _ in 0..ticks {
+            world.run_schedule(schedule);
+        }
+        world.resource_mut::<Resimulating>().0 = false;
+        *world.resource_mut::<Time>() = time;
+        world.resource_mut::<RollbackSkipped>().0.clear();
+    }
+
+    fn reset(mut tick: ResMut<PredictionTick>, mut rollback_tick: ResMut<RollbackTick>) {
+        *tick = Default::default();
+        rollback_tick.0 = None;
+    }
+}
+
+/// Prediction functions for [`App`].
+pub trait AppPredictionExt {
+    /// Enables prediction for component `C` on entities marked with [`Predicted`].
+    ///
+    /// Component should be registered for replication separately.
+    /// Requires [`PredictionPlugin`].
+    fn predict<C: Component + Clone + PartialEq>(&mut self) -> &mut Self;
+}
+
+impl AppPredictionExt for App {
+    fn predict<C: Component + Clone + PartialEq>(&mut self) -> &mut Self {
+        let mut registry = self.world_mut().resource_mut::<PredictionRegistry>();
+        registry.rollback_fns.push(RollbackFns {
+            collect_unrecorded: collect_unrecorded::<C>,
+            restore: restore::<C>,
+            snap: snap::<C>,
+        });
+        let schedule = registry.schedule;
+
+        self.set_marker_fns::<Predicted, C>(write_predicted::<C>, remove_predicted::<C>)
+            .add_systems(schedule, record::<C>.in_set(PredictionSet::Record))
+    }
+}
+
+/// Default schedule for [`PredictionPlugin::schedule`].
+///
+/// Runs inside [`FixedUpdate`] and re-runs on rollback without other [`FixedUpdate`] systems.
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct PredictionSchedule;
+
+/// Marks entity as predicted.
+///
+/// Registered components will be recorded into [`PredictionHistory`] and compared with values
+/// received from the server. See [`PredictionPlugin`] for details.
+///
+/// Should be present before the entity receives its first update, so it's usually inserted on
+/// pre-spawned entities or in [`ClientSet::Receive`] using the blueprint pattern.
+#[derive(Component, Default, Clone, Copy)]
+pub struct Predicted;
+
+/// Tick simulated by the current [`PredictionPlugin::schedule`] run.
+///
+/// Set to [`ClientTick`] at the beginning of each run. During rollback, set to the confirmed tick
+/// and incremented for each re-simulated tick.
+///
+/// Reset on disconnect.
+#[derive(Clone, Copy, Deref, Debug, Default, Resource)]
+pub struct PredictionTick(RepliconTick);
+
+/// Predicted entities that are skipped during rollback re-simulation.
+///
+/// Contains entities without a recorded value at or before the rollback tick.
+/// Simulation systems should ignore them, since their state can't be restored.
+/// Empty outside of re-simulation.
+#[derive(Resource, Default, Deref)]
+pub struct RollbackSkipped(EntityHashSet);
+
+/// Set with prediction systems inside [`PredictionPlugin::schedule`].
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum PredictionSet {
+    /// Updates [`PredictionTick`].
+    Tick,
+    /// Systems that simulate predicted entities.
+    ///
+    /// Runs after [`PredictionSet::Tick`].
+    Simulate,
+    /// Records component values into [`PredictionHistory`].
+    ///
+    /// Runs after [`PredictionSet::Simulate`].
+    Record,
+}
+
+/// Recorded values of component `C` for predicted entities, sorted by tick.
+///
+/// Inserted automatically when the first authoritative value is received.
+#[derive(Component, Deref)]
+pub struct PredictionHistory<C>(VecDeque<(RepliconTick, C)>);
+
+impl<C: Clone> PredictionHistory<C> {
+    /// Returns the latest value recorded at or before `tick`.
+    pub fn value_at(&self, tick: RepliconTick) -> Option<&C> {
+        self.iter()
+            .rev()
+            .find(|&&(value_tick, _)| value_tick <= tick)
+            .map(|(_, value)| value)
+    }
+
+    /// Records value for `tick`, replacing all values recorded at or after it.
+    fn insert(&mut self, tick: RepliconTick, value: C, history_len: usize) {
+        self.truncate_after(tick);
+        if self.back().is_some_and(|&(last_tick, _)| last_tick == tick) {
+            self.0.pop_back();
+        }
+        self.0.push_back((tick, value));
+
+        while self.len() > history_len {
+            self.0.pop_front();
+        }
+    }
+
+    /// Removes all values recorded after `tick`.
+    fn truncate_after(&mut self, tick: RepliconTick) {
+        while self.back().is_some_and(|&(last_tick, _)| last_tick > tick) {
+            self.0.pop_back();
+        }
+    }
+
+    /// Removes all values recorded before `tick`.
+    fn confirm(&mut self, tick: RepliconTick) {
+        while self
+            .front()
+            .is_some_and(|&(first_tick, _)| first_tick < tick)
+        {
+            self.0.pop_front();
+        }
+    }
+}
+
+/// Tick to roll back to after receiving replication.
+///
+/// Contains the oldest mismatched tick.
+#[derive(Resource, Default)]
+struct RollbackTick(Option<RepliconTick>);
+
+/// Indicates that [`PredictionPlugin::schedule`] is re-run on rollback.
+#[derive(Resource, Default, Deref)]
+struct Resimulating(bool);
+
+impl RollbackTick {
+    fn request(&mut self, tick: RepliconTick) {
+        if self.0.is_none_or(|rollback_tick| tick < rollback_tick) {
+            self.0 = Some(tick);
+        }
+    }
+}
+
+#[derive(Resource)]
+struct PredictionRegistry {
+    schedule: InternedScheduleLabel,
+    history_len: usize,
+    rollback_fns: Vec<RollbackFns>,
+}
+
+/// Type-erased rollback functions for a predicted component.
+#[derive(Clone, Copy)]
+struct RollbackFns {
+    /// Collects predicted entities without a recorded value at or before the specified tick.
+    collect_unrecorded: fn(&mut World, RepliconTick, &mut EntityHashSet),
+
+    /// Restores predicted components to their recorded values at the specified tick, except for the passed entities.
+    restore: fn(&mut World, RepliconTick, &EntityHashSet),
+
+    /// Sets predicted components to the confirmed values at the specified tick if they were mismatched.
+    snap: fn(&mut World, RepliconTick),
+}
+
+/// Compares received value with the recorded prediction and requests rollback on a mismatch.
+fn write_predicted<C: Component + Clone + PartialEq>(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<C>,
+    entity: &mut DeferredEntity,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    let component: C = rule_fns.deserialize(ctx, cursor)?;
+    let history_len = entity.world().resource::<PredictionRegistry>().history_len;
+    let tick = ctx.message_tick;
+    let entity_id = entity.id();
+
+    if let Some(mut history) = entity.get_mut::<PredictionHistory<C>>() {
+        if history
+            .front()
+            .is_some_and(|&(first_tick, _)| tick < first_tick)
+        {
+            trace!("ignoring outdated value for {tick:?}");
+            return Ok(());
+        }
+
+        if history
+            .iter()
+            .find(|&&(value_tick, _)| value_tick == tick)
+            .is_some_and(|(_, value)| *value == component)
+        {
+            history.confirm(tick);
+            return Ok(());
+        }
+
+        debug!("mismatched prediction for `{entity_id}` at {tick:?}");
+        history.confirm(tick);
+        history.insert(tick, component, history_len);
+    } else {
+        trace!("inserting first confirmed value for `{entity_id}` at {tick:?}");
+        ctx.commands.entity(entity_id).insert((
+            component.clone(),
+            PredictionHistory([(tick, component)].into()),
+        ));
+        return Ok(());
+    }
+
+    ctx.commands.queue(move |world: &mut World| {
+        world.resource_mut::<RollbackTick>().request(tick);
+    });
+
+    Ok(())
+}
+
+/// Removes component `C` and its history.
+fn remove_predicted<C: Component>(ctx: &mut RemoveCtx, entity: &mut DeferredEntity) {
+    ctx.commands
+        .entity(entity.id())
+        .remove::<(C, PredictionHistory<C>)>();
+}
+
+fn collect_unrecorded<C: Component + Clone>(
+    world: &mut World,
+    tick: RepliconTick,
+    skipped: &mut EntityHashSet,
+) {
+    let mut predicted = world
+        .query_filtered::<(Entity, Option<&PredictionHistory<C>>), (With<C>, With<Predicted>)>();
+    for (entity, history) in predicted.iter(world) {
+        if history.is_none_or(|history| history.value_at(tick).is_none()) {
+            skipped.insert(entity);
+        }
+    }
+}
+
+fn restore<C: Component + Clone>(world: &mut World, tick: RepliconTick, skipped: &EntityHashSet) {
+    let mut predicted =
+        world.query_filtered::<(Entity, &mut C, &mut PredictionHistory<C>), With<Predicted>>();
+    for (entity, mut component, mut history) in predicted.iter_mut(world) {
+        if skipped.contains(&entity) {
+            continue;
+        }
+
+        history.truncate_after(tick);
+        if let Some(value) = history.value_at(tick) {
+            *component = value.clone();
+        }
+    }
+}
+
+fn snap<C: Component + Clone>(world: &mut World, tick: RepliconTick) {
+    let mut predicted = world.query_filtered::<(&mut C, &PredictionHistory<C>), With<Predicted>>();
+    for (mut component, history) in predicted.iter_mut(world) {
+        // Mismatched values replace all values recorded after them.
+        if let Some((_, value)) = history.back().filter(|&&(last_tick, _)| last_tick <= tick) {
+            *component = value.clone();
+        }
+    }
+}
+
+fn record<C: Component + Clone>(
+    tick: Res<PredictionTick>,
+    registry: Res<PredictionRegistry>,
+    skipped: Res<RollbackSkipped>,
+    mut predicted: Query<(Entity, &C, &mut PredictionHistory<C>), With<Predicted>>,
+) {
+    for (entity, component, mut history) in &mut predicted {
+        if skipped.contains(&entity) {
+            continue;
+        }
+        history.insert(**tick, component.clone(), registry.history_len);
+    }
+}
@@ -0,0 +1,179 @@
+use bevy::{
+    ecs::{component::Tick, entity::EntityHashSet, world::CommandQueue},
+    prelude::*,
+    utils::Duration,
+};
+
+use super::{ClientPlugin, ClientSet, ServerUpdateTick};
+use crate::{
+    client,
+    core::{
+        common_conditions::client_just_connected,
+        replication::{
+            command_markers::{CommandMarkers, EntityMarkers},
+            deferred_entity::DeferredEntity,
+            replication_registry::{ctx::DespawnCtx, ReplicationRegistry},
+        },
+        server_entity_map::ServerEntityMap,
+    },
+};
+
+/// Keeps client entities across reconnects and repairs them after the first received update.
+///
+/// On disconnect all replicated entities are kept. After reconnect the server sends
+/// all visible entities in a fresh update message. Entities from this message are matched against
+/// the kept ones by server entity. Kept entities that the server no longer sends are despawned
+/// via [`ReplicationRegistry::despawn`] and replicated components that weren't re-inserted are removed
+/// the same way as during a resync.
+///
+/// The server doesn't send update messages if there is nothing to replicate, so if no update message
+/// arrives within [`Self::timeout`] after reconnect, all kept entities are considered stale.
+///
+/// If the client is not going to reconnect, kept entities need to be despawned manually.
+///
+/// Should be added after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct ClientRepairPlugin {
+    /// Time to wait for the first update message after reconnect before repairing without it.
+    ///
+    /// Should be large enough for the server to start replication for the client.
+    pub timeout: Duration,
+}
+
+impl Default for ClientRepairPlugin {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl Plugin for ClientRepairPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(RepairState {
+            entities: Default::default(),
+            since: None,
+            started: Default::default(),
+            timeout: self.timeout,
+        })
+        .add_systems(
+            PreUpdate,
+            (
+                Self::stash
+                    .in_set(ClientSet::Reset)
+                    .before(ClientPlugin::reset),
+                Self::start
+                    .before(ClientSet::Receive)
+                    .run_if(client_just_connected),
+                Self::repair
+                    .after(ClientSet::Receive)
+                    .before(ClientSet::Diagnostics)
+                    .run_if(repair_pending),
+            ),
+        );
+    }
+}
+
+impl ClientRepairPlugin {
+    fn stash(mut entity_map: ResMut<ServerEntityMap>, mut state: ResMut<RepairState>) {
+        // Could be disconnected before the previous repair finished.
+        state
+            .entities
+            .extend(entity_map.to_client().values().copied());
+        entity_map.stash();
+        state.since = None;
+        debug!("stashing {} entities for repair", state.entities.len());
+    }
+
+    fn start(world: &mut World) {
+        let change_tick = world.change_tick();
+        let elapsed = world.resource::<Time<Real>>().elapsed();
+        let mut state = world.resource_mut::<RepairState>();
+        if !state.entities.is_empty() {
+            state.since = Some(change_tick);
+            state.started = elapsed;
+        }
+    }
+
+    fn repair(
+        world: &mut World,
+        mut queue: Local<CommandQueue>,
+        mut entity_markers: Local<EntityMarkers>,
+    ) {
+        let stale_entities = world.resource_mut::<ServerEntityMap>().take_stashed();
+        let mut state = world.resource_mut::<RepairState>();
+        let since = state.since.take().expect("repair should start on connect");
+        let mut entities = std::mem::take(&mut state.entities);
+        for stale_entity in stale_entities.values() {
+            entities.remove(stale_entity);
+        }
+
+        world.resource_scope(|world, registry: Mut<ReplicationRegistry>| {
+            world.resource_scope(|world, command_markers: Mut<CommandMarkers>| {
+                let message_tick = **world.resource::<ServerUpdateTick>();
+                let ctx = DespawnCtx { message_tick };
+                for &client_entity in stale_entities.values() {
+                    if let Ok(entity) = world.get_entity_mut(client_entity) {
+                        (registry.despawn)(&ctx, entity);
+                    }
+                }
+
+                let this_run = world.change_tick();
+                for client_entity in entities {
+                    if world.get_entity(client_entity).is_err() {
+                        continue;
+                    }
+
+                    let mut client_entity = DeferredEntity::new(world, client_entity);
+                    let mut commands = client_entity.commands(&mut queue);
+                    entity_markers.read(&command_markers, &*client_entity);
+                    // Components that weren't re-inserted since reconnect are missing on the server.
+                    client::remove_stale_components(
+                        &mut commands,
+                        &entity_markers,
+                        &registry,
+                        &mut client_entity,
+                        message_tick,
+                        |component_id, entity| {
+                            entity
+                                .get_change_ticks_by_id(component_id)
+                                .is_some_and(|ticks| !ticks.is_changed(since, this_run))
+                        },
+                    );
+
+                    queue.apply(world);
+                }
+            })
+        });
+
+        debug!("despawned {} stale entities", stale_entities.len());
+    }
+}
+
+/// Returns `true` if the repair is required and the first update message after reconnect
+/// was received or [`ClientRepairPlugin::timeout`] has elapsed.
+fn repair_pending(
+    state: Res<RepairState>,
+    update_tick: Res<ServerUpdateTick>,
+    time: Res<Time<Real>>,
+) -> bool {
+    state.since.is_some()
+        && (**update_tick != Default::default()
+            || time.elapsed().saturating_sub(state.started) >= state.timeout)
+}
+
+/// Entities kept from the previous connection.
+#[derive(Resource)]
+struct RepairState {
+    entities: EntityHashSet,
+
+    /// Change tick at the moment of reconnect.
+    ///
+    /// Used to detect components that weren't re-inserted.
+    since: Option<Tick>,
+
+    /// Elapsed time at the moment of reconnect.
+    started: Duration,
+
+    /// See [`ClientRepairPlugin::timeout`].
+    timeout: Duration,
+}
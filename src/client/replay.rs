@@ -0,0 +1,233 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use bincode::{DefaultOptions, ErrorKind, Options};
+use bytes::Bytes;
+
+use super::ClientSet;
+use crate::core::{
+    common_conditions::client_connected,
+    replicon_client::{RepliconClient, RepliconClientStatus},
+};
+
+/// Records and replays messages received from the server.
+///
+/// Insert [`ReplayRecorder`] to record all messages received on server channels
+/// with their receive timestamps. Remove it to stop recording.
+///
+/// Insert [`ReplayPlayer`] to feed recorded messages back into [`RepliconClient`] with
+/// the original timing. This allows a headless client to rebuild the recorded world state without
+/// a server. The client should register the same replicated components and events in the same order.
+///
+/// Should be added after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            (
+                Self::play
+                    .in_set(ClientSet::ReceivePackets)
+                    .run_if(resource_exists::<ReplayPlayer>),
+                Self::record
+                    .after(ClientSet::ReceivePackets)
+                    .before(ClientSet::Receive)
+                    .run_if(client_connected)
+                    .run_if(resource_exists::<ReplayRecorder>),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            Self::discard_sent
+                .in_set(ClientSet::SendPackets)
+                .run_if(resource_exists::<ReplayPlayer>),
+        );
+    }
+}
+
+impl ReplayPlugin {
+    fn record(
+        mut recorder: ResMut<ReplayRecorder>,
+        client: Res<RepliconClient>,
+        time: Res<Time<Real>>,
+    ) {
+        let start = *recorder.start.get_or_insert(time.elapsed());
+        let timestamp = time.elapsed() - start;
+        for (channel_id, message) in client.iter_received() {
+            if let Err(e) = recorder.write(timestamp, channel_id, message) {
+                error!("unable to record message over channel {channel_id}: {e}");
+            }
+        }
+
+        if let Err(e) = recorder.writer.flush() {
+            error!("unable to flush recorded messages: {e}");
+        }
+    }
+
+    fn play(
+        mut player: ResMut<ReplayPlayer>,
+        mut client: ResMut<RepliconClient>,
+        time: Res<Time<Real>>,
+    ) {
+        if client.is_disconnected() {
+            debug!("starting replay");
+            client.set_status(RepliconClientStatus::Connected { client_id: None });
+        }
+
+        let start = *player.start.get_or_insert(time.elapsed());
+        let elapsed = time.elapsed() - start;
+        loop {
+            match player.peek() {
+                Ok(Some(record)) if record.timestamp <= elapsed => {
+                    let record = player.next.take().expect("record should be peeked");
+                    // Messages are recorded after decoding, so the transformation shouldn't be applied again.
+                    client.insert_decoded(record.channel_id, record.message);
+                }
+                Ok(_) => break,
+                Err(e) => {
+                    error!("unable to read recorded message: {e}");
+                    player.finished = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Drains messages sent by the client since there is no server to receive them.
+    fn discard_sent(mut client: ResMut<RepliconClient>) {
+        client.drain_sent().for_each(drop);
+    }
+}
+
+/// Writes all messages received from the server.
+///
+/// See [`ReplayPlugin`] for details.
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    writer: Box<dyn Write + Send + Sync>,
+    start: Option<Duration>,
+}
+
+impl ReplayRecorder {
+    /// Creates a new recorder that writes into the specified writer.
+    pub fn new(mut writer: impl Write + Send + Sync + 'static) -> io::Result<Self> {
+        writer.write_all(&MAGIC)?;
+
+        Ok(Self {
+            writer: Box::new(writer),
+            start: None,
+        })
+    }
+
+    /// Creates a new recorder that writes into a file.
+    ///
+    /// The file will be truncated if it already exists.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Self::new(BufWriter::new(file))
+    }
+
+    fn write(
+        &mut self,
+        timestamp: Duration,
+        channel_id: u8,
+        message: &[u8],
+    ) -> bincode::Result<()> {
+        DefaultOptions::new().serialize_into(&mut self.writer, &(timestamp, channel_id, message))
+    }
+}
+
+/// Feeds recorded messages into [`RepliconClient`].
+///
+/// Sets the client status to connected on the first update.
+/// Recorded messages are already decoded, so
+/// [`RepliconChannels::set_message_transform`](crate::core::channels::RepliconChannels::set_message_transform)
+/// isn't applied to them.
+/// See [`ReplayPlugin`] for details.
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    reader: Box<dyn Read + Send + Sync>,
+    next: Option<Record>,
+    start: Option<Duration>,
+    finished: bool,
+}
+
+impl ReplayPlayer {
+    /// Creates a new player that reads from the specified reader.
+    pub fn new(mut reader: impl Read + Send + Sync + 'static) -> io::Result<Self> {
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "data is not a replicon recording",
+            ));
+        }
+
+        Ok(Self {
+            reader: Box::new(reader),
+            next: None,
+            start: None,
+            finished: false,
+        })
+    }
+
+    /// Creates a new player that reads from a file.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Self::new(BufReader::new(file))
+    }
+
+    /// Returns `true` if all recorded messages were fed to the client.
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.next.is_none()
+    }
+
+    /// Returns the next record without consuming it.
+    fn peek(&mut self) -> bincode::Result<Option<&Record>> {
+        if self.next.is_none() && !self.finished {
+            match DefaultOptions::new()
+                .with_limit(MAX_RECORD_SIZE)
+                .deserialize_from(&mut self.reader)
+            {
+                Ok((timestamp, channel_id, message)) => {
+                    let message: Vec<u8> = message;
+                    self.next = Some(Record {
+                        timestamp,
+                        channel_id,
+                        message: Bytes::from(message),
+                    });
+                }
+                Err(e) => match *e {
+                    ErrorKind::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                        debug!("replay finished");
+                        self.finished = true;
+                    }
+                    _ => return Err(e),
+                },
+            }
+        }
+
+        Ok(self.next.as_ref())
+    }
+}
+
+/// Identifies the recording format.
+const MAGIC: [u8; 4] = *b"RPL1";
+
+/// Maximum size of a single record.
+///
+/// Larger records are considered corrupted.
+const MAX_RECORD_SIZE: u64 = 64 * 1024 * 1024;
+
+struct Record {
+    timestamp: Duration,
+    channel_id: u8,
+    message: Bytes,
+}
@@ -0,0 +1,279 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{confirm_history::EntityReplicated, ClientSet, ServerUpdateTick};
+use crate::core::{
+    common_conditions::client_connected, replicon_client::RepliconClient,
+    replicon_tick::RepliconTick,
+};
+
+/// Estimates the current server tick on client.
+///
+/// Each received tick is shifted by a half of [`RepliconClient::rtt`] and used to correct
+/// [`ServerClock`], which otherwise advances by [`Time<Real>`].
+///
+/// If [`Self::fixed_lead`] is set, also keeps [`ClientTick`] at the specified distance from the estimated
+/// server tick by slightly adjusting [`Time<Fixed>`] timestep.
+///
+/// Should be added after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct ServerClockPlugin {
+    /// Duration of a single server tick.
+    ///
+    /// Also used by [`InterpolationPlugin`](super::interpolation::InterpolationPlugin) if added.
+    pub tick_duration: Duration,
+
+    /// Fraction of the estimation error corrected per received tick.
+    ///
+    /// Should be in range `0..=1`. Lower values make the estimation smoother, but slower to react
+    /// to latency changes.
+    pub smoothing: f64,
+
+    /// Number of ticks to keep [`ClientTick`] ahead of the server.
+    ///
+    /// Use negative values to stay behind. If [`None`], [`ClientTick`] won't be tracked.
+    pub fixed_lead: Option<i32>,
+}
+
+impl Default for ServerClockPlugin {
+    fn default() -> Self {
+        Self {
+            tick_duration: Duration::from_secs_f64(1.0 / 64.0),
+            smoothing: 0.1,
+            fixed_lead: None,
+        }
+    }
+}
+
+impl Plugin for ServerClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ServerClock {
+            tick_duration: self.tick_duration,
+            smoothing: self.smoothing,
+            synced: false,
+            latest_tick: None,
+            tick: Default::default(),
+            overstep: 0.0,
+            elapsed: Duration::ZERO,
+        })
+        .add_systems(
+            PreUpdate,
+            (
+                Self::advance.before(ClientSet::Receive),
+                Self::receive
+                    .after(ClientSet::Receive)
+                    .before(ClientSet::Diagnostics)
+                    .run_if(client_connected),
+            ),
+        )
+        .add_systems(PreUpdate, Self::reset.in_set(ClientSet::Reset));
+
+        if let Some(lead) = self.fixed_lead {
+            app.init_resource::<ClientTick>()
+                .add_systems(FixedFirst, Self::increment_client_tick)
+                .add_systems(
+                    PreUpdate,
+                    sync_fixed_system(lead)
+                        .after(Self::receive)
+                        .before(ClientSet::Diagnostics),
+                );
+        }
+    }
+}
+
+impl ServerClockPlugin {
+    fn advance(mut clock: ResMut<ServerClock>, time: Res<Time<Real>>) {
+        clock.elapsed = time.elapsed();
+        if clock.synced {
+            let ticks = time.delta().as_secs_f64() / clock.tick_duration.as_secs_f64();
+            clock.shift(ticks);
+        }
+    }
+
+    fn receive(
+        mut clock: ResMut<ServerClock>,
+        mut replicated_events: EventReader<EntityReplicated>,
+        client: Res<RepliconClient>,
+        update_tick: Res<ServerUpdateTick>,
+    ) {
+        let mut received = None;
+        // Default tick is set on reset and never sent by the server.
+        let update_tick = (update_tick.is_changed() && **update_tick != RepliconTick::default())
+            .then_some(**update_tick);
+        for tick in replicated_events
+            .read()
+            .map(|event| event.tick)
+            .chain(update_tick)
+        {
+            if received.is_none_or(|received| tick > received) {
+                received = Some(tick);
+            }
+        }
+
+        if let Some(tick) = received {
+            clock.receive(tick, client.rtt());
+        }
+    }
+
+    fn increment_client_tick(mut client_tick: ResMut<ClientTick>) {
+        client_tick.0 += 1;
+        trace!("incremented {client_tick:?}");
+    }
+
+    fn reset(mut clock: ResMut<ServerClock>, client_tick: Option<ResMut<ClientTick>>) {
+        clock.synced = false;
+        clock.latest_tick = None;
+        clock.tick = Default::default();
+        clock.overstep = 0.0;
+        if let Some(mut client_tick) = client_tick {
+            *client_tick = Default::default();
+        }
+    }
+}
+
+/// Estimation error in ticks after which the clock snaps instead of smooth correction.
+const SNAP_THRESHOLD: f64 = 8.0;
+
+/// Maximum relative change of [`Time<Fixed>`] timestep to catch up with the server.
+const MAX_SPEED_ADJUSTMENT: f64 = 0.1;
+
+/// Smoothed estimation of the current server tick.
+///
+/// Updated in [`PreUpdate`] after [`ClientSet::Receive`]. See [`ServerClockPlugin`] for details.
+///
+/// Reset on disconnect.
+#[derive(Resource)]
+pub struct ServerClock {
+    tick_duration: Duration,
+    smoothing: f64,
+    synced: bool,
+    latest_tick: Option<RepliconTick>,
+    tick: RepliconTick,
+    overstep: f64,
+    elapsed: Duration,
+}
+
+impl ServerClock {
+    /// Returns the estimated current server tick.
+    pub fn tick(&self) -> RepliconTick {
+        self.tick
+    }
+
+    /// Returns the fraction of the current server tick that has elapsed.
+    pub fn overstep(&self) -> f64 {
+        self.overstep
+    }
+
+    /// Returns `true` if at least one tick was received and the estimation is available.
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Returns the estimated difference in seconds between the server time and [`Time<Real>::elapsed`].
+    ///
+    /// Server time is calculated as the number of elapsed ticks multiplied by their duration.
+    pub fn time_offset(&self) -> f64 {
+        let server_time =
+            (self.tick.get() as f64 + self.overstep) * self.tick_duration.as_secs_f64();
+        server_time - self.elapsed.as_secs_f64()
+    }
+
+    /// Returns the duration of a single server tick.
+    pub fn tick_duration(&self) -> Duration {
+        self.tick_duration
+    }
+
+    fn receive(&mut self, tick: RepliconTick, rtt: f64) {
+        if self
+            .latest_tick
+            .is_some_and(|latest_tick| tick <= latest_tick)
+        {
+            return;
+        }
+        self.latest_tick = Some(tick);
+
+        // The server was at the received tick half of RTT ago.
+        let latency = rtt / 2.0 / self.tick_duration.as_secs_f64();
+        let error = ticks_between(self.tick, tick) as f64 + latency - self.overstep;
+        if !self.synced || error.abs() > SNAP_THRESHOLD {
+            trace!("snapping server clock from {:?} to {tick:?}", self.tick);
+            self.synced = true;
+            self.tick = tick;
+            self.overstep = 0.0;
+            self.shift(latency);
+        } else {
+            self.shift(error * self.smoothing);
+        }
+    }
+
+    /// Moves the clock by the specified number of ticks.
+    fn shift(&mut self, ticks: f64) {
+        self.overstep += ticks;
+        let whole = self.overstep.floor();
+        self.overstep -= whole;
+        self.tick += whole as i32 as u32;
+    }
+}
+
+/// Tick of the client fixed timestep.
+///
+/// Incremented in [`FixedFirst`] and kept at [`ServerClockPlugin::fixed_lead`] ticks from [`ServerClock`].
+/// Available only if the lead is set.
+///
+/// Reset on disconnect.
+#[derive(Clone, Copy, Deref, Debug, Default, Resource)]
+pub struct ClientTick(RepliconTick);
+
+/// Creates a system that adjusts [`Time<Fixed>`] to keep [`ClientTick`] at `lead` ticks from [`ServerClock`].
+fn sync_fixed_system(
+    lead: i32,
+) -> impl FnMut(Res<ServerClock>, ResMut<ClientTick>, ResMut<Time<Fixed>>) {
+    let mut synced = false;
+    move |clock, mut client_tick, mut fixed_time| {
+        if !clock.is_synced() {
+            synced = false;
+            return;
+        }
+
+        let target = clock.tick + lead as u32;
+        let error = ticks_between(**client_tick, target) as f64 + clock.overstep
+            - fixed_time.overstep_fraction_f64();
+        if !synced || error.abs() > SNAP_THRESHOLD {
+            synced = true;
+            trace!(
+                "snapping client tick from {:?} to {target:?}",
+                **client_tick
+            );
+            client_tick.0 = target;
+            fixed_time.set_timestep(clock.tick_duration);
+        } else {
+            let speed =
+                1.0 + (error * clock.smoothing).clamp(-MAX_SPEED_ADJUSTMENT, MAX_SPEED_ADJUSTMENT);
+            fixed_time.set_timestep(clock.tick_duration.div_f64(speed));
+        }
+    }
+}
+
+/// Returns `true` if [`ServerClock`] has an estimation.
+pub fn server_clock_synced(clock: Option<Res<ServerClock>>) -> bool {
+    clock.is_some_and(|clock| clock.is_synced())
+}
+
+/// Returns `true` if the estimated server tick changed since the last run.
+pub fn server_tick_advanced(
+    mut last_tick: Local<Option<RepliconTick>>,
+    clock: Option<Res<ServerClock>>,
+) -> bool {
+    let Some(clock) = clock.filter(|clock| clock.is_synced()) else {
+        return false;
+    };
+
+    let advanced = *last_tick != Some(clock.tick());
+    *last_tick = Some(clock.tick());
+    advanced
+}
+
+/// Returns signed number of ticks from `from` to `to`, taking wrapping into account.
+fn ticks_between(from: RepliconTick, to: RepliconTick) -> i32 {
+    (to - from) as i32
+}
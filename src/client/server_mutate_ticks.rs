@@ -8,7 +8,8 @@ use crate::core::replicon_tick::RepliconTick;
 ///
 /// For efficiency we store only the last received tick and
 /// an array indicating whether all mutate messages for the most
-/// recent 64 ticks were received.
+/// recent ticks were received. By default it covers 64 ticks, but it can be
+/// extended via [`ClientPlugin::history_len`](super::ClientPlugin::history_len).
 ///
 /// Inserted to the world in [`ClientPlugin::finish`](super::ClientPlugin::finish) if
 /// [`TrackAppExt::track_mutate_messages`](crate::core::replication::track_mutate_messages::TrackAppExt::track_mutate_messages)
@@ -24,16 +25,29 @@ pub struct ServerMutateTicks {
 }
 
 impl ServerMutateTicks {
+    /// Creates a new instance that tracks `len` ticks.
+    pub(super) fn new(len: usize) -> Self {
+        Self {
+            ticks: VecDeque::from(vec![Default::default(); len.max(1)]),
+            last_tick: Default::default(),
+        }
+    }
+
+    /// Returns the number of tracked ticks.
+    pub fn history_len(&self) -> usize {
+        self.ticks.len()
+    }
+
     /// Returns the last received tick.
     pub fn last_tick(&self) -> RepliconTick {
         self.last_tick
     }
 
-    /// Returns a mask that represents the received ticks.
+    /// Returns a mask that represents the most recent 64 received ticks.
     pub fn mask(&self) -> u64 {
         let mut bitmask = 0;
 
-        for (i, tick) in self.ticks.iter().enumerate() {
+        for (i, tick) in self.ticks.iter().take(u64::BITS as usize).enumerate() {
             if tick.all_received() {
                 bitmask |= 1 << i;
             }
@@ -44,7 +58,7 @@ impl ServerMutateTicks {
 
     /// Returns `true` if this tick is confirmed for an entity.
     ///
-    /// All ticks older then 64 ticks since [`Self::last_tick`] are considered received.
+    /// All ticks older then [`Self::history_len`] ticks since [`Self::last_tick`] are considered received.
     pub fn contains(&self, tick: RepliconTick) -> bool {
         if tick > self.last_tick {
             return false;
@@ -61,7 +75,7 @@ impl ServerMutateTicks {
     /// Returns `true` if any tick in the given range was confirmed for the entity with
     /// this component.
     ///
-    /// All ticks older then 64 ticks since [`Self::last_tick`] are considered received.
+    /// All ticks older then [`Self::history_len`] ticks since [`Self::last_tick`] are considered received.
     ///
     /// # Panics
     ///
@@ -108,7 +122,7 @@ impl ServerMutateTicks {
             if ago >= len {
                 // If the difference exceeds the size, clear all ticks.
                 self.ticks.clear();
-                self.ticks.resize(u64::BITS as usize, Default::default());
+                self.ticks.resize(len, Default::default());
             } else {
                 for _ in 0..ago {
                     self.ticks.pop_back();
@@ -131,10 +145,7 @@ impl ServerMutateTicks {
 
 impl Default for ServerMutateTicks {
     fn default() -> Self {
-        Self {
-            ticks: VecDeque::from([Default::default(); u64::BITS as usize]),
-            last_tick: Default::default(),
-        }
+        Self::new(u64::BITS as usize)
     }
 }
 
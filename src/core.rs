@@ -1,8 +1,10 @@
+pub mod authority;
 pub mod channels;
 pub mod common_conditions;
 pub mod connected_clients;
 pub mod entity_serde;
 pub mod event;
+pub mod message_transform;
 pub mod replication;
 pub mod replicon_client;
 pub mod replicon_server;
@@ -12,11 +14,12 @@ pub mod server_entity_map;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use channels::RepliconChannels;
-use event::event_registry::EventRegistry;
+use channels::{ChannelKind, RepliconChannels};
+use event::{client_event::ClientEventAppExt, event_registry::EventRegistry};
 use replication::{
     command_markers::CommandMarkers, replication_registry::ReplicationRegistry,
-    replication_rules::ReplicationRules, track_mutate_messages::TrackMutateMessages, Replicated,
+    replication_rules::ReplicationRules, replication_scope::ReplicationScope,
+    resync_request::ResyncRequest, track_mutate_messages::TrackMutateMessages, Replicated,
 };
 
 /// Initializes types and resources needed for both client and server.
@@ -25,12 +28,14 @@ pub struct RepliconCorePlugin;
 impl Plugin for RepliconCorePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Replicated>()
+            .register_type::<ReplicationScope>()
             .init_resource::<TrackMutateMessages>()
             .init_resource::<RepliconChannels>()
             .init_resource::<ReplicationRegistry>()
             .init_resource::<ReplicationRules>()
             .init_resource::<CommandMarkers>()
-            .init_resource::<EventRegistry>();
+            .init_resource::<EventRegistry>()
+            .add_client_event::<ResyncRequest>(ChannelKind::Ordered);
     }
 }
 
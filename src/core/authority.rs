@@ -0,0 +1,212 @@
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    replication::replication_rules::AppRuleExt, replicon_client::RepliconClient, ClientId,
+};
+#[cfg(feature = "client")]
+use crate::client::ClientSet;
+#[cfg(feature = "server")]
+use crate::server::ServerEvent;
+
+/// Enables entity ownership via [`Owner`].
+///
+/// Registers [`Owner`] for replication and maintains the [`Owned`] marker.
+/// On the server, also handles owned entities of disconnected clients according to [`OwnerDisconnectPolicy`].
+///
+/// Should be added after [`RepliconPlugins`](crate::RepliconPlugins).
+pub struct OwnershipPlugin;
+
+impl Plugin for OwnershipPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Owner>().replicate::<Owner>();
+
+        let update_owned = update_owned;
+        #[cfg(feature = "client")]
+        let update_owned = update_owned.after(ClientSet::Receive);
+        app.add_systems(PreUpdate, update_owned);
+
+        #[cfg(feature = "server")]
+        app.init_resource::<OwnerDisconnectPolicy>()
+            .add_observer(handle_disconnects);
+    }
+}
+
+/// A replicated component that indicates which client owns the entity.
+///
+/// Requires [`OwnershipPlugin`]. Use it for input routing, visibility or permission checks.
+/// On the server it can be inserted directly or changed via [`OwnershipCommandsExt`].
+///
+/// Entities owned by the local client get the [`Owned`] marker. On a server or in singleplayer
+/// the local client is [`ClientId::SERVER`].
+///
+/// When the owning client disconnects, the server handles its entities according to [`OwnerDisconnectPolicy`].
+#[derive(
+    Component, Deref, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect,
+)]
+#[reflect(Component)]
+pub struct Owner(pub ClientId);
+
+/// Marks entities whose [`Owner`] matches the local client.
+///
+/// Inserted and removed automatically in [`PreUpdate`] after receiving replication.
+///
+/// See also [`owns_any`](super::common_conditions::owns_any) and
+/// [`owns_any_with`](super::common_conditions::owns_any_with) run conditions.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Owned;
+
+/// Defines what happens with entities owned by a client when it disconnects.
+///
+/// Applied on the server for all entities with [`Owner`] that matches the disconnected client.
+///
+/// Inserted as resource by [`OwnershipPlugin`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OwnerDisconnectPolicy {
+    /// Despawn owned entities recursively.
+    #[default]
+    Despawn,
+    /// Remove [`Owner`] from owned entities.
+    RemoveOwner,
+    /// Keep owned entities unchanged.
+    ///
+    /// Useful if the client is expected to reconnect and you want to restore the ownership manually.
+    Keep,
+}
+
+/// Triggered on the server when the ownership of an entity changes via [`OwnershipCommandsExt`].
+///
+/// See also [`Trigger`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnershipTransferred {
+    /// Entity whose ownership changed.
+    pub entity: Entity,
+
+    /// Owner before the transfer.
+    pub previous: Option<ClientId>,
+
+    /// Owner after the transfer.
+    pub owner: Option<ClientId>,
+}
+
+/// Extension for [`EntityCommands`] to change the entity [`Owner`].
+pub trait OwnershipCommandsExt {
+    /// Transfers the entity ownership to a client and triggers [`OwnershipTransferred`].
+    ///
+    /// Does nothing if the client already owns the entity.
+    fn transfer_ownership(&mut self, owner: ClientId) -> &mut Self;
+
+    /// Removes [`Owner`] from the entity and triggers [`OwnershipTransferred`].
+    ///
+    /// Does nothing if the entity has no owner.
+    fn revoke_ownership(&mut self) -> &mut Self;
+}
+
+impl OwnershipCommandsExt for EntityCommands<'_> {
+    fn transfer_ownership(&mut self, owner: ClientId) -> &mut Self {
+        self.queue(move |entity: Entity, world: &mut World| {
+            set_owner(world, entity, Some(owner));
+        })
+    }
+
+    fn revoke_ownership(&mut self) -> &mut Self {
+        self.queue(|entity: Entity, world: &mut World| {
+            set_owner(world, entity, None);
+        })
+    }
+}
+
+fn set_owner(world: &mut World, entity: Entity, owner: Option<ClientId>) {
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        warn!("trying to change owner of {entity:?}, but the entity doesn't exist");
+        return;
+    };
+
+    let previous = entity_mut.get::<Owner>().map(|owner| **owner);
+    if previous == owner {
+        return;
+    }
+
+    match owner {
+        Some(owner) => {
+            entity_mut.insert(Owner(owner));
+        }
+        None => {
+            entity_mut.remove::<Owner>();
+        }
+    }
+
+    debug!("transferring ownership of {entity:?} from `{previous:?}` to `{owner:?}`");
+    world.trigger(OwnershipTransferred {
+        entity,
+        previous,
+        owner,
+    });
+}
+
+/// Applies [`OwnerDisconnectPolicy`] to entities owned by a disconnected client.
+#[cfg(feature = "server")]
+fn handle_disconnects(
+    trigger: Trigger<ServerEvent>,
+    mut commands: Commands,
+    owner_policy: Res<OwnerDisconnectPolicy>,
+    owners: Query<(Entity, &Owner)>,
+) {
+    let ServerEvent::ClientDisconnected { client_id, .. } = *trigger.event() else {
+        return;
+    };
+
+    let owned = owners
+        .iter()
+        .filter(|(_, owner)| ***owner == client_id)
+        .map(|(entity, _)| entity);
+    match *owner_policy {
+        OwnerDisconnectPolicy::Despawn => {
+            for entity in owned {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        OwnerDisconnectPolicy::RemoveOwner => {
+            for entity in owned {
+                commands.entity(entity).remove::<Owner>();
+            }
+        }
+        OwnerDisconnectPolicy::Keep => (),
+    }
+}
+
+/// Inserts or removes [`Owned`] based on [`Owner`] and the local client ID.
+fn update_owned(
+    mut commands: Commands,
+    mut last_id: Local<Option<ClientId>>,
+    mut removed_owners: RemovedComponents<Owner>,
+    owners: Query<(Entity, Ref<Owner>, Has<Owned>)>,
+    client: Option<Res<RepliconClient>>,
+) {
+    let local_id = match client {
+        Some(client) if !client.is_disconnected() => client.id(),
+        _ => Some(ClientId::SERVER),
+    };
+
+    let id_changed = *last_id != local_id;
+    *last_id = local_id;
+
+    for entity in removed_owners.read() {
+        if let Some(mut entity) = commands.get_entity(entity) {
+            entity.remove::<Owned>();
+        }
+    }
+
+    for (entity, owner, owned) in &owners {
+        if !id_changed && !owner.is_changed() {
+            continue;
+        }
+
+        let owns = Some(**owner) == local_id;
+        if owns && !owned {
+            commands.entity(entity).insert(Owned);
+        } else if !owns && owned {
+            commands.entity(entity).remove::<Owned>();
+        }
+    }
+}
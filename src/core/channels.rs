@@ -1,14 +1,19 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use bevy::prelude::*;
 
+use super::message_transform::MessageTransform;
+
 /// ID of a server replication channel.
 ///
 /// See also [`RepliconChannels`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[repr(u8)]
 pub enum ReplicationChannel {
     /// For sending messages with entity mappings, inserts, removals and despawns.
     ///
+    /// Also used to send the reason passed to [`RepliconServer::disconnect`](crate::core::replicon_server::RepliconServer::disconnect).
+    ///
     /// This is an ordered reliable channel.
     Updates,
     /// For sending messages with component mutations.
@@ -19,9 +24,15 @@ pub enum ReplicationChannel {
 
 impl From<ReplicationChannel> for RepliconChannel {
     fn from(value: ReplicationChannel) -> Self {
-        match value {
-            ReplicationChannel::Updates => ChannelKind::Ordered.into(),
-            ReplicationChannel::Mutations => ChannelKind::Unreliable.into(),
+        let kind = match value {
+            ReplicationChannel::Updates => ChannelKind::Ordered,
+            ReplicationChannel::Mutations => ChannelKind::Unreliable,
+        };
+
+        Self {
+            // Prioritize replication over events by default.
+            priority: 1,
+            ..kind.into()
         }
     }
 }
@@ -46,6 +57,35 @@ pub struct RepliconChannels {
     /// This value will be used instead of [`None`].
     /// By default set to `5 * 1024 * 1024`.
     pub default_max_bytes: usize,
+
+    /// Maximum number of bytes per second that the server can send to a single client.
+    ///
+    /// If set, messages that exceed the limit will be dropped for [`ChannelKind::Unreliable`]
+    /// channels and postponed to the next frames for reliable channels.
+    /// Messages from channels with higher [`RepliconChannel::priority`] are sent first.
+    ///
+    /// A message is sent while the client budget is positive, so a message larger than the remaining
+    /// budget will make it negative. This debt is carried over and repaid by the next refills.
+    ///
+    /// Message sizes are counted before [`Self::set_message_transform`] is applied,
+    /// so any overhead added by the transformation isn't included in the limit.
+    ///
+    /// By default set to [`None`], which means unlimited.
+    pub max_client_bps: Option<usize>,
+
+    /// Maximum number of bytes in reliable messages that can be postponed for a single client
+    /// due to [`Self::max_client_bps`].
+    ///
+    /// If a client exceeds this limit, its postponed messages will be dropped and a disconnect
+    /// will be requested via [`RepliconServer::disconnect`](crate::core::replicon_server::RepliconServer::disconnect).
+    ///
+    /// By default set to `5 * 1024 * 1024`.
+    pub max_client_postponed_bytes: usize,
+
+    /// Transformation applied to all messages.
+    ///
+    /// See also [`Self::set_message_transform`].
+    message_transform: Option<Arc<dyn MessageTransform>>,
 }
 
 /// Only stores the replication channel by default.
@@ -61,6 +101,9 @@ impl Default for RepliconChannels {
                 ReplicationChannel::Mutations.into(),
             ],
             default_max_bytes: 5 * 1024 * 1024,
+            max_client_bps: None,
+            max_client_postponed_bytes: 5 * 1024 * 1024,
+            message_transform: None,
         }
     }
 }
@@ -71,6 +114,32 @@ impl RepliconChannels {
         self.default_max_bytes = max_bytes;
     }
 
+    /// Sets the maximum number of bytes per second that the server can send to a single client.
+    ///
+    /// See also [`Self::max_client_bps`].
+    pub fn set_max_client_bps(&mut self, max_client_bps: Option<usize>) {
+        self.max_client_bps = max_client_bps;
+    }
+
+    /// Sets the maximum number of postponed bytes for a single client.
+    ///
+    /// See also [`Self::max_client_postponed_bytes`].
+    pub fn set_max_client_postponed_bytes(&mut self, max_bytes: usize) {
+        self.max_client_postponed_bytes = max_bytes;
+    }
+
+    /// Sets a transformation that will be applied to all sent and received messages.
+    ///
+    /// Should be called before the app starts.
+    pub fn set_message_transform(&mut self, transform: impl MessageTransform) {
+        self.message_transform = Some(Arc::new(transform));
+    }
+
+    /// Returns the transformation set by [`Self::set_message_transform`].
+    pub fn message_transform(&self) -> Option<&Arc<dyn MessageTransform>> {
+        self.message_transform.as_ref()
+    }
+
     /// Creates a new server channel and returns its ID.
     ///
     /// # Panics
@@ -149,11 +218,20 @@ pub struct RepliconChannel {
     ///
     /// If unset, the default value from [`RepliconChannels`] will be used.
     pub max_bytes: Option<usize>,
+
+    /// Sending priority for the channel.
+    ///
+    /// Messages from channels with higher priority are drained from
+    /// [`RepliconServer`](crate::core::replicon_server::RepliconServer) first.
+    /// Matters only for server channels when [`RepliconChannels::max_client_bps`] is set.
+    ///
+    /// By default set to `0` for events and `1` for replication channels.
+    pub priority: u8,
 }
 
 /// Channel delivery guarantee.
 ///
-/// Can be automatically converted into [`RepliconChannel`] with zero resend time and default max bytes.
+/// Can be automatically converted into [`RepliconChannel`] with zero resend time, default max bytes and zero priority.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ChannelKind {
     /// Unreliable and unordered.
@@ -170,6 +248,7 @@ impl From<ChannelKind> for RepliconChannel {
             kind: value,
             resend_time: Duration::ZERO,
             max_bytes: None,
+            priority: 0,
         }
     }
 }
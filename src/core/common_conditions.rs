@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use super::{replicon_client::RepliconClient, replicon_server::RepliconServer};
+use super::{
+    authority::Owned,
+    replicon_client::{DisconnectReason, RepliconClient},
+    replicon_server::RepliconServer,
+};
 
 /// Returns `true` if the server is running.
 pub fn server_running(server: Option<Res<RepliconServer>>) -> bool {
@@ -72,3 +76,58 @@ pub fn client_just_disconnected(
     *last_not_disconnected = !disconnected;
     just_disconnected
 }
+
+/// Returns a [`Condition`]-satisfying closure that returns `true` when the client is disconnected
+/// on this tick with a reason that matches the predicate.
+///
+/// Useful for UI systems that need to react to a specific disconnect reason.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_replicon::prelude::*;
+///
+/// # let mut app = App::new();
+/// app.add_systems(
+///     Update,
+///     show_timeout_message
+///         .run_if(client_just_disconnected_with(|reason| *reason == DisconnectReason::TimedOut)),
+/// );
+///
+/// fn show_timeout_message() {
+///     // ...
+/// }
+/// ```
+pub fn client_just_disconnected_with(
+    predicate: impl Fn(&DisconnectReason) -> bool + Send + Sync + 'static,
+) -> impl FnMut(Local<bool>, Option<Res<RepliconClient>>) -> bool {
+    move |mut last_not_disconnected: Local<bool>, client: Option<Res<RepliconClient>>| {
+        let disconnected = client
+            .as_ref()
+            .is_some_and(|client| client.is_disconnected());
+
+        let just_disconnected = *last_not_disconnected && disconnected;
+        *last_not_disconnected = !disconnected;
+        just_disconnected
+            && client
+                .and_then(|client| client.disconnect_reason().map(&predicate))
+                .unwrap_or_default()
+    }
+}
+
+/// Returns `true` if the local client owns at least one entity.
+///
+/// See also [`Owned`].
+pub fn owns_any(owned: Query<(), With<Owned>>) -> bool {
+    !owned.is_empty()
+}
+
+/// Returns `true` if the local client owns at least one entity with component `C`.
+///
+/// Useful for owner-only systems, like input handling for the local player.
+///
+/// See also [`Owned`].
+pub fn owns_any_with<C: Component>(owned: Query<(), (With<Owned>, With<C>)>) -> bool {
+    !owned.is_empty()
+}
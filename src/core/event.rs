@@ -1,4 +1,7 @@
 pub mod client_event;
+pub mod client_request;
+pub mod client_trigger;
 pub mod ctx;
 pub(crate) mod event_registry;
 pub mod server_event;
+pub mod server_trigger;
@@ -130,31 +130,44 @@ impl ClientEventAppExt for App {
         serialize: SerializeFn<E>,
         deserialize: DeserializeFn<E>,
     ) -> &mut Self {
-        debug!("registering event `{}`", any::type_name::<E>());
-
-        self.add_event::<E>()
-            .add_event::<FromClient<E>>()
-            .init_resource::<ClientEventReader<E>>();
-
-        let channel_id = self
-            .world_mut()
-            .resource_mut::<RepliconChannels>()
-            .create_client_channel(channel.into());
-
-        self.world_mut()
-            .resource_scope(|world, mut event_registry: Mut<EventRegistry>| {
-                event_registry.register_client_event(ClientEvent::new(
-                    world.components(),
-                    channel_id,
-                    serialize,
-                    deserialize,
-                ));
-            });
-
-        self
+        add_client_event_as::<E, E>(self, channel, serialize, deserialize)
     }
 }
 
+/// Like [`ClientEventAppExt::add_client_event_with`], but identifies the event by `T`.
+///
+/// Used for internal wrappers, so users can configure
+/// [`ClientEventLimiter`](crate::server::event_limits::ClientEventLimiter) for the wrapped type.
+pub(super) fn add_client_event_as<E: Event, T: 'static>(
+    app: &mut App,
+    channel: impl Into<RepliconChannel>,
+    serialize: SerializeFn<E>,
+    deserialize: DeserializeFn<E>,
+) -> &mut App {
+    debug!("registering event `{}`", any::type_name::<E>());
+
+    app.add_event::<E>()
+        .add_event::<FromClient<E>>()
+        .init_resource::<ClientEventReader<E>>();
+
+    let channel_id = app
+        .world_mut()
+        .resource_mut::<RepliconChannels>()
+        .create_client_channel(channel.into());
+
+    app.world_mut()
+        .resource_scope(|world, mut event_registry: Mut<EventRegistry>| {
+            event_registry.register_client_event(ClientEvent::new::<E, T>(
+                world.components(),
+                channel_id,
+                serialize,
+                deserialize,
+            ));
+        });
+
+    app
+}
+
 /// Type-erased functions and metadata for a registered client event.
 ///
 /// Needed so events of different types can be processed together.
@@ -162,6 +175,12 @@ pub(crate) struct ClientEvent {
     event_id: TypeId,
     event_name: &'static str,
 
+    /// Type by which the event is identified for limits.
+    ///
+    /// Differs from the event type for internal wrappers.
+    limits_id: TypeId,
+    limits_name: &'static str,
+
     /// ID of [`Events<E>`] resource.
     events_id: ComponentId,
 
@@ -183,7 +202,7 @@ pub(crate) struct ClientEvent {
 }
 
 impl ClientEvent {
-    fn new<E: Event>(
+    fn new<E: Event, T: 'static>(
         components: &Components,
         channel_id: u8,
         serialize: SerializeFn<E>,
@@ -215,6 +234,8 @@ impl ClientEvent {
         Self {
             event_id: TypeId::of::<E>(),
             event_name: any::type_name::<E>(),
+            limits_id: TypeId::of::<T>(),
+            limits_name: any::type_name::<T>(),
             events_id,
             reader_id,
             client_events_id,
@@ -229,6 +250,18 @@ impl ClientEvent {
         }
     }
 
+    pub(crate) fn limits_id(&self) -> TypeId {
+        self.limits_id
+    }
+
+    pub(crate) fn limits_name(&self) -> &'static str {
+        self.limits_name
+    }
+
+    pub(crate) fn channel_id(&self) -> u8 {
+        self.channel_id
+    }
+
     pub(crate) fn events_id(&self) -> ComponentId {
         self.events_id
     }
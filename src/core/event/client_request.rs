@@ -0,0 +1,315 @@
+use std::{any, marker::PhantomData, time::Duration};
+
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{
+    client_event::{self, FromClient},
+    server_event::{SendMode, ServerEventAppExt, ToClients},
+};
+#[cfg(feature = "client")]
+use crate::client::ClientSet;
+use crate::core::channels::RepliconChannel;
+
+/// An extension trait for [`App`] for creating client requests.
+pub trait ClientRequestAppExt {
+    /**
+    Registers a request `Req` from client with a response `Resp` from server.
+
+    Built on top of client and server events. Registers [`Request<Req>`] as a client event
+    and [`Response<Req, Resp>`] as a server event using the same channel for both.
+
+    On client, send requests via [`RequestSender`] and read [`RequestOutcome`] events.
+    On server, read [`FromClient<Request<Req>>`] events and answer them with [`FromClient::respond`].
+    In listen-server mode requests and responses are re-emitted locally just like regular events.
+
+    Each request type should be registered only once. Limits for received requests can be configured for `Req` via
+    [`ClientEventLimiter`](crate::server::event_limits::ClientEventLimiter).
+    Pending requests time out after [`PendingRequests::timeout`] and are discarded on disconnect.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_client_request::<BuyItem, BuyResult>(ChannelKind::Ordered)
+        .add_systems(
+            PreUpdate,
+            answer_requests
+                .after(ServerSet::Receive)
+                .run_if(server_or_singleplayer),
+        )
+        .add_systems(PreUpdate, read_outcomes.after(ClientSet::Receive))
+        .add_systems(PostUpdate, send_requests.before(ClientSet::Send));
+
+    fn send_requests(mut requests: RequestSender<BuyItem>) {
+        let id = requests.send(BuyItem(42));
+        info!("sent request {id:?}");
+    }
+
+    fn answer_requests(
+        mut requests: EventReader<FromClient<Request<BuyItem>>>,
+        mut responses: EventWriter<ToClients<Response<BuyItem, BuyResult>>>,
+    ) {
+        for request in requests.read() {
+            responses.send(request.respond(BuyResult::Accepted));
+        }
+    }
+
+    fn read_outcomes(mut outcomes: EventReader<RequestOutcome<BuyItem, BuyResult>>) {
+        for outcome in outcomes.read() {
+            match &outcome.response {
+                Some(response) => info!("received {response:?} for {:?}", outcome.id),
+                None => info!("request {:?} timed out", outcome.id),
+            }
+        }
+    }
+
+    #[derive(Event, Deserialize, Serialize)]
+    struct BuyItem(usize);
+
+    #[derive(Debug, Deserialize, Serialize)]
+    enum BuyResult {
+        Accepted,
+        Rejected,
+    }
+    ```
+    */
+    fn add_client_request<Req, Resp>(&mut self, channel: impl Into<RepliconChannel>) -> &mut Self
+    where
+        Req: Event + Serialize + DeserializeOwned,
+        Resp: Send + Sync + Serialize + DeserializeOwned + 'static;
+}
+
+impl ClientRequestAppExt for App {
+    fn add_client_request<Req, Resp>(&mut self, channel: impl Into<RepliconChannel>) -> &mut Self
+    where
+        Req: Event + Serialize + DeserializeOwned,
+        Resp: Send + Sync + Serialize + DeserializeOwned + 'static,
+    {
+        debug!(
+            "registering request `{}` with response `{}`",
+            any::type_name::<Req>(),
+            any::type_name::<Resp>()
+        );
+
+        let channel = channel.into();
+        client_event::add_client_event_as::<Request<Req>, Req>(
+            self,
+            channel.clone(),
+            client_event::default_serialize,
+            client_event::default_deserialize,
+        )
+        .add_server_event::<Response<Req, Resp>>(channel)
+        .add_event::<RequestOutcome<Req, Resp>>()
+        .init_resource::<PendingRequests<Req>>();
+
+        let receive_responses = receive_responses::<Req, Resp>;
+        #[cfg(feature = "client")]
+        let receive_responses = receive_responses.after(ClientSet::Receive);
+        self.add_systems(PreUpdate, receive_responses);
+
+        #[cfg(feature = "client")]
+        self.add_systems(PreUpdate, reset::<Req, Resp>.in_set(ClientSet::Reset));
+
+        self
+    }
+}
+
+/// Unique ID of a request within its type.
+///
+/// Assigned automatically by [`RequestSender`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct RequestId(u32);
+
+/// A request from client that expects a [`Response`] from server.
+///
+/// See also [`ClientRequestAppExt::add_client_request`].
+#[derive(Clone, Copy, Debug, Event, Deserialize, Serialize)]
+pub struct Request<Req> {
+    /// ID assigned by [`RequestSender`] to match the response.
+    pub id: RequestId,
+    /// Request data.
+    pub request: Req,
+}
+
+impl<Req> FromClient<Request<Req>> {
+    /// Creates a response to this request that will be sent only to the requesting client.
+    pub fn respond<Resp>(&self, response: Resp) -> ToClients<Response<Req, Resp>> {
+        ToClients {
+            mode: SendMode::Direct(self.client_id),
+            event: Response {
+                id: self.event.id,
+                response,
+                marker: PhantomData,
+            },
+        }
+    }
+}
+
+/// A response from server to [`Request<Req>`].
+///
+/// Create it using [`FromClient::respond`].
+/// On client it will be emitted as [`RequestOutcome`] if the request is still pending.
+#[derive(Clone, Copy, Debug, Event, Deserialize, Serialize)]
+pub struct Response<Req, Resp> {
+    /// ID of the request this response belongs to.
+    pub id: RequestId,
+    /// Response data.
+    pub response: Resp,
+    marker: PhantomData<Req>,
+}
+
+/// The result of a request sent via [`RequestSender`].
+#[derive(Clone, Copy, Debug, Event)]
+pub struct RequestOutcome<Req, Resp> {
+    /// ID returned by [`RequestSender::send`].
+    pub id: RequestId,
+    /// Response from server or [`None`] if no response was received within [`PendingRequests::timeout`]
+    /// or the client disconnected before receiving it.
+    ///
+    /// Responses that arrive after the timeout are ignored.
+    pub response: Option<Resp>,
+    marker: PhantomData<Req>,
+}
+
+/// Sends requests from client and tracks them in [`PendingRequests`].
+#[derive(SystemParam)]
+pub struct RequestSender<'w, Req: Event> {
+    requests: EventWriter<'w, Request<Req>>,
+    pending: ResMut<'w, PendingRequests<Req>>,
+}
+
+impl<Req: Event> RequestSender<'_, Req> {
+    /// Sends a request to server and returns its assigned ID.
+    pub fn send(&mut self, request: Req) -> RequestId {
+        let id = self.pending.next_id();
+        self.requests.send(Request { id, request });
+        id
+    }
+}
+
+/// Requests of type `Req` that are waiting for a response from server.
+#[derive(Resource)]
+pub struct PendingRequests<Req> {
+    /// Time passed since sending for each pending request.
+    requests: HashMap<RequestId, Duration>,
+    next_id: u32,
+    timeout: Duration,
+    marker: PhantomData<Req>,
+}
+
+impl<Req> PendingRequests<Req> {
+    /// Returns `true` if a response for the request hasn't been received and the request hasn't timed out yet.
+    pub fn contains(&self, id: RequestId) -> bool {
+        self.requests.contains_key(&id)
+    }
+
+    /// Returns the number of pending requests.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Returns `true` if there are no pending requests.
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Returns the time after which a pending request will be considered timed out.
+    ///
+    /// By default it's 10 seconds.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Sets the timeout for pending requests.
+    ///
+    /// See also [`Self::timeout`].
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    fn next_id(&mut self) -> RequestId {
+        let id = RequestId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.requests.insert(id, Duration::ZERO);
+        id
+    }
+}
+
+impl<Req> Default for PendingRequests<Req> {
+    fn default() -> Self {
+        Self {
+            requests: Default::default(),
+            next_id: 0,
+            timeout: Duration::from_secs(10),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Matches received responses with pending requests and expires requests without a response.
+fn receive_responses<Req: Event, Resp: Send + Sync + 'static>(
+    mut responses: ResMut<Events<Response<Req, Resp>>>,
+    mut outcomes: EventWriter<RequestOutcome<Req, Resp>>,
+    mut pending: ResMut<PendingRequests<Req>>,
+    time: Res<Time>,
+) {
+    for Response { id, response, .. } in responses.drain() {
+        if pending.requests.remove(&id).is_some() {
+            outcomes.send(RequestOutcome {
+                id,
+                response: Some(response),
+                marker: PhantomData,
+            });
+        } else {
+            debug!(
+                "ignoring response for `{}` with {id:?} that is not pending",
+                any::type_name::<Req>()
+            );
+        }
+    }
+
+    let timeout = pending.timeout;
+    pending.requests.retain(|&id, elapsed| {
+        *elapsed += time.delta();
+        if *elapsed < timeout {
+            return true;
+        }
+
+        debug!(
+            "request `{}` with {id:?} timed out",
+            any::type_name::<Req>()
+        );
+        outcomes.send(RequestOutcome {
+            id,
+            response: None,
+            marker: PhantomData,
+        });
+        false
+    });
+}
+
+/// Discards all pending requests on disconnect, emitting [`RequestOutcome`] without a response for each.
+#[cfg(feature = "client")]
+fn reset<Req: Event, Resp: Send + Sync + 'static>(
+    mut outcomes: EventWriter<RequestOutcome<Req, Resp>>,
+    mut pending: ResMut<PendingRequests<Req>>,
+) {
+    if !pending.is_empty() {
+        debug!(
+            "discarding {} pending requests `{}` on disconnect",
+            pending.len(),
+            any::type_name::<Req>()
+        );
+    }
+    outcomes.send_batch(pending.requests.drain().map(|(id, _)| RequestOutcome {
+        id,
+        response: None,
+        marker: PhantomData,
+    }));
+}
@@ -0,0 +1,168 @@
+use std::{any, io::Cursor};
+
+use bevy::{ecs::observer::TriggerTargets, prelude::*};
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{
+    client_event::{self, FromClient},
+    ctx::{ClientSendCtx, ServerReceiveCtx},
+};
+use crate::core::channels::RepliconChannel;
+#[cfg(feature = "server")]
+use crate::server::ServerSet;
+
+/// An extension trait for [`App`] for creating client triggers.
+pub trait ClientTriggerAppExt {
+    /**
+    Registers `E` as a trigger that can be sent from client to server.
+
+    On server `E` will be triggered as [`FromClient<E>`] for observers after [`ServerSet::Receive`].
+    Trigger targets are mapped from client to server entities using
+    [`ServerEntityMap`](crate::core::server_entity_map::ServerEntityMap), so all targets
+    should be replicated from the server. The event itself is sent as is, use
+    [`ClientEventAppExt::add_mapped_client_event`](super::client_event::ClientEventAppExt::add_mapped_client_event)
+    if you need to map entities inside the event.
+
+    Limits for received triggers can be configured for `E` via
+    [`ClientEventLimiter`](crate::server::event_limits::ClientEventLimiter).
+
+    Send triggers using [`ClientTriggerExt`]. In listen-server mode they will be re-triggered
+    locally as [`FromClient<E>`] with [`ClientId::SERVER`](crate::core::ClientId::SERVER).
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_client_trigger::<Jump>(ChannelKind::Ordered)
+        .add_observer(receive_jumps)
+        .add_systems(Update, send_jumps.run_if(client_connected));
+
+    fn send_jumps(mut commands: Commands) {
+        commands.client_trigger(Jump);
+    }
+
+    fn receive_jumps(trigger: Trigger<FromClient<Jump>>) {
+        info!("received jump from {:?}", trigger.client_id);
+    }
+
+    #[derive(Event, Deserialize, Serialize)]
+    struct Jump;
+    ```
+    */
+    fn add_client_trigger<E: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+}
+
+impl ClientTriggerAppExt for App {
+    fn add_client_trigger<E: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        debug!("registering trigger `{}`", any::type_name::<E>());
+
+        client_event::add_client_event_as::<_, E>(
+            self,
+            channel,
+            serialize_trigger::<E>,
+            deserialize_trigger::<E>,
+        );
+
+        let trigger_received = trigger_received::<E>;
+        #[cfg(feature = "server")]
+        let trigger_received = trigger_received.after(ServerSet::Receive);
+        self.add_systems(PreUpdate, trigger_received)
+    }
+}
+
+/// An extension trait for [`Commands`] for sending client triggers.
+///
+/// See also [`ClientTriggerAppExt::add_client_trigger`].
+pub trait ClientTriggerExt {
+    /// Sends a trigger to server without targets.
+    fn client_trigger(&mut self, event: impl Event);
+
+    /// Sends a trigger to server for the specified targets.
+    ///
+    /// Only entity targets are supported. Targets without a mapping to a server entity are skipped with a warning.
+    fn client_trigger_targets(&mut self, event: impl Event, targets: impl TriggerTargets);
+}
+
+impl ClientTriggerExt for Commands<'_, '_> {
+    fn client_trigger(&mut self, event: impl Event) {
+        self.client_trigger_targets(event, ());
+    }
+
+    fn client_trigger_targets(&mut self, event: impl Event, targets: impl TriggerTargets) {
+        self.send_event(ClientTriggerEvent {
+            event,
+            targets: targets.entities().to_vec(),
+        });
+    }
+}
+
+/// Wrapper that sends a trigger with its targets as a client event.
+#[derive(Event)]
+struct ClientTriggerEvent<E> {
+    event: E,
+    targets: Vec<Entity>,
+}
+
+fn serialize_trigger<E: Event + Serialize>(
+    ctx: &mut ClientSendCtx,
+    trigger: &ClientTriggerEvent<E>,
+    message: &mut Vec<u8>,
+) -> bincode::Result<()> {
+    let targets: Vec<_> = trigger
+        .targets
+        .iter()
+        .filter_map(|&entity| {
+            let server_entity = ctx.entity_map.to_server().get(&entity).copied();
+            if server_entity.is_none() {
+                warn!(
+                    "skipping target {entity:?} for `{}` because it has no server mapping",
+                    any::type_name::<E>()
+                );
+            }
+            server_entity
+        })
+        .collect();
+    DefaultOptions::new().serialize_into(&mut *message, &trigger.event)?;
+    DefaultOptions::new().serialize_into(message, &targets)
+}
+
+fn deserialize_trigger<E: Event + DeserializeOwned>(
+    _ctx: &mut ServerReceiveCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<ClientTriggerEvent<E>> {
+    let event = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    let targets = DefaultOptions::new().deserialize_from(cursor)?;
+    Ok(ClientTriggerEvent { event, targets })
+}
+
+/// Triggers received events for observers.
+fn trigger_received<E: Event>(
+    mut commands: Commands,
+    mut events: ResMut<Events<FromClient<ClientTriggerEvent<E>>>>,
+) {
+    for FromClient { client_id, event } in events.drain() {
+        debug!(
+            "triggering `{}` from `{client_id:?}`",
+            any::type_name::<E>()
+        );
+        commands.trigger_targets(
+            FromClient {
+                client_id,
+                event: event.event,
+            },
+            event.targets,
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use std::{any, io::Cursor};
+
+use bevy::{
+    ecs::{entity::EntityMapper, observer::TriggerTargets},
+    prelude::*,
+};
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{
+    ctx::{ClientReceiveCtx, ServerSendCtx},
+    server_event::{ServerEventAppExt, ToClients},
+};
+#[cfg(feature = "client")]
+use crate::client::ClientSet;
+use crate::core::channels::RepliconChannel;
+
+/// An extension trait for [`App`] for creating server triggers.
+pub trait ServerTriggerAppExt {
+    /**
+    Registers `E` as a trigger that can be sent from server to clients.
+
+    On client `E` will be triggered for observers after [`ClientSet::Receive`].
+    Trigger targets are mapped from server to client entities using
+    [`ServerEntityMap`](crate::core::server_entity_map::ServerEntityMap). If any target
+    isn't replicated to the client, the trigger will be discarded. The event itself is sent as is,
+    use [`ServerEventAppExt::add_mapped_server_event`] if you need to map entities inside the event.
+
+    Send triggers using [`ServerTriggerExt`]. On listen server or in singleplayer they will be
+    re-triggered locally if [`ClientId::SERVER`](crate::core::ClientId::SERVER) is not excluded
+    from the send list.
+
+    # Examples
+
+    ```
+    use bevy::prelude::*;
+    use bevy_replicon::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins));
+    app.add_server_trigger::<Explosion>(ChannelKind::Unordered)
+        .add_observer(receive_explosions)
+        .add_systems(Update, send_explosions.run_if(server_running));
+
+    fn send_explosions(mut commands: Commands) {
+        commands.server_trigger(ToClients {
+            mode: SendMode::Broadcast,
+            event: Explosion,
+        });
+    }
+
+    fn receive_explosions(_trigger: Trigger<Explosion>) {
+        info!("received explosion from server");
+    }
+
+    #[derive(Event, Deserialize, Serialize)]
+    struct Explosion;
+    ```
+    */
+    fn add_server_trigger<E: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self;
+}
+
+impl ServerTriggerAppExt for App {
+    fn add_server_trigger<E: Event + Serialize + DeserializeOwned>(
+        &mut self,
+        channel: impl Into<RepliconChannel>,
+    ) -> &mut Self {
+        debug!("registering trigger `{}`", any::type_name::<E>());
+
+        self.add_server_event_with(channel, serialize_trigger::<E>, deserialize_trigger::<E>);
+
+        let trigger_received = trigger_received::<E>;
+        #[cfg(feature = "client")]
+        let trigger_received = trigger_received.after(ClientSet::Receive);
+        self.add_systems(PreUpdate, trigger_received)
+    }
+}
+
+/// An extension trait for [`Commands`] for sending server triggers.
+///
+/// See also [`ServerTriggerAppExt::add_server_trigger`].
+pub trait ServerTriggerExt {
+    /// Sends a trigger to clients without targets.
+    fn server_trigger(&mut self, event: ToClients<impl Event>);
+
+    /// Sends a trigger to clients for the specified targets.
+    ///
+    /// Only entity targets are supported.
+    fn server_trigger_targets(
+        &mut self,
+        event: ToClients<impl Event>,
+        targets: impl TriggerTargets,
+    );
+}
+
+impl ServerTriggerExt for Commands<'_, '_> {
+    fn server_trigger(&mut self, event: ToClients<impl Event>) {
+        self.server_trigger_targets(event, ());
+    }
+
+    fn server_trigger_targets(
+        &mut self,
+        event: ToClients<impl Event>,
+        targets: impl TriggerTargets,
+    ) {
+        self.send_event(ToClients {
+            mode: event.mode,
+            event: ServerTriggerEvent {
+                event: event.event,
+                targets: targets.entities().to_vec(),
+            },
+        });
+    }
+}
+
+/// Wrapper that sends a trigger with its targets as a server event.
+#[derive(Event)]
+struct ServerTriggerEvent<E> {
+    event: E,
+    targets: Vec<Entity>,
+}
+
+fn serialize_trigger<E: Event + Serialize>(
+    _ctx: &mut ServerSendCtx,
+    trigger: &ServerTriggerEvent<E>,
+    message: &mut Vec<u8>,
+) -> bincode::Result<()> {
+    DefaultOptions::new().serialize_into(&mut *message, &trigger.event)?;
+    DefaultOptions::new().serialize_into(message, &trigger.targets)
+}
+
+fn deserialize_trigger<E: Event + DeserializeOwned>(
+    ctx: &mut ClientReceiveCtx,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<ServerTriggerEvent<E>> {
+    let event = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    let targets: Vec<Entity> = DefaultOptions::new().deserialize_from(cursor)?;
+    let targets = targets
+        .into_iter()
+        .map(|entity| ctx.map_entity(entity))
+        .collect();
+
+    Ok(ServerTriggerEvent { event, targets })
+}
+
+/// Triggers received events for observers.
+fn trigger_received<E: Event>(
+    mut commands: Commands,
+    mut events: ResMut<Events<ServerTriggerEvent<E>>>,
+) {
+    for event in events.drain() {
+        debug!("triggering `{}` from server", any::type_name::<E>());
+        commands.trigger_targets(event.event, event.targets);
+    }
+}
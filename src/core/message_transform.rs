@@ -0,0 +1,64 @@
+use bytes::Bytes;
+
+use crate::core::ClientId;
+
+/**
+Transforms messages between Replicon and the messaging backend.
+
+Can be used to authenticate or encrypt messages above the transport layer,
+for example when messages travel over relays.
+
+Every message passes through [`Self::encode`] when it's drained with
+[`RepliconServer::drain_sent`](super::replicon_server::RepliconServer::drain_sent) or
+[`RepliconClient::drain_sent`](super::replicon_client::RepliconClient::drain_sent).
+And through [`Self::decode`] when it's inserted with
+[`RepliconServer::insert_received`](super::replicon_server::RepliconServer::insert_received) or
+[`RepliconClient::insert_received`](super::replicon_client::RepliconClient::insert_received).
+
+Messages are transformed per client, so each session can use its own key.
+On server the client ID is the ID of the sender or receiver. On client it's the ID
+from [`RepliconClient::id`](super::replicon_client::RepliconClient::id), so both sides
+use the same key. The backend must assign the client ID when transformation is used, otherwise
+all messages on client will be dropped.
+
+Register with [`RepliconChannels::set_message_transform`](super::channels::RepliconChannels::set_message_transform).
+
+# Examples
+
+```
+use bevy::prelude::*;
+use bevy_replicon::{core::message_transform::MessageTransform, prelude::*};
+use bytes::Bytes;
+
+# let mut app = App::new();
+# app.add_plugins(RepliconPlugins);
+app.world_mut()
+    .resource_mut::<RepliconChannels>()
+    .set_message_transform(Xor);
+
+/// Toy transform that XORs all bytes with a key.
+///
+/// Replace with a proper cipher.
+struct Xor;
+
+impl MessageTransform for Xor {
+    fn encode(&self, client_id: ClientId, _channel_id: u8, message: Bytes) -> Bytes {
+        let key = client_id.get() as u8;
+        message.iter().map(|byte| byte ^ key).collect()
+    }
+
+    fn decode(&self, client_id: ClientId, _channel_id: u8, message: Bytes) -> Option<Bytes> {
+        Some(self.encode(client_id, 0, message))
+    }
+}
+```
+**/
+pub trait MessageTransform: Send + Sync + 'static {
+    /// Transforms an outgoing message.
+    fn encode(&self, client_id: ClientId, channel_id: u8, message: Bytes) -> Bytes;
+
+    /// Reverts [`Self::encode`] for an incoming message.
+    ///
+    /// Returns [`None`] if the message can't be decoded, in which case it will be dropped.
+    fn decode(&self, client_id: ClientId, channel_id: u8, message: Bytes) -> Option<Bytes>;
+}
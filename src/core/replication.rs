@@ -1,8 +1,11 @@
 pub mod command_markers;
+pub mod component_events;
 pub mod deferred_entity;
 pub mod replicated_clients;
 pub mod replication_registry;
 pub mod replication_rules;
+pub mod replication_scope;
+pub mod resync_request;
 pub mod track_mutate_messages;
 pub mod update_message_flags;
 
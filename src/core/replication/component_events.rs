@@ -0,0 +1,158 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use super::replication_registry::ReplicationRegistry;
+use crate::core::replicon_tick::RepliconTick;
+
+/// Per-component replication events for [`App`].
+pub trait AppComponentEventExt {
+    /// Enables [`ComponentReplicated<C>`] and [`ComponentRemovedByServer<C>`] events for component `C`.
+    ///
+    /// Unlike Bevy's change detection, these events are sent only for changes received from the server.
+    /// They are sent on client regardless of the used write or remove functions, but only if the component
+    /// was actually inserted, changed or removed. So values that a write function discards, like
+    /// outdated mutations or values that prediction stores in a history, don't send events.
+    /// If the component was already changed during the same tick, the event is sent for any received value.
+    ///
+    /// Component should be registered for replication separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_replicon::prelude::*;
+    /// # use serde::{Deserialize, Serialize};
+    ///
+    /// # let mut app = App::new();
+    /// # app.add_plugins(RepliconPlugins);
+    /// app.replicate::<Health>()
+    ///     .add_component_events::<Health>()
+    ///     .add_systems(PreUpdate, read_health.after(ClientSet::Receive));
+    ///
+    /// fn read_health(mut health_events: EventReader<ComponentReplicated<Health>>) {
+    ///     for event in health_events.read() {
+    ///         info!("received health for `{}` at {:?}", event.entity, event.tick);
+    ///     }
+    /// }
+    ///
+    /// # #[derive(Component, Deserialize, Serialize)]
+    /// # struct Health(u32);
+    /// ```
+    fn add_component_events<C: Component>(&mut self) -> &mut Self;
+}
+
+impl AppComponentEventExt for App {
+    fn add_component_events<C: Component>(&mut self) -> &mut Self {
+        self.add_event::<ComponentReplicated<C>>()
+            .add_event::<ComponentRemovedByServer<C>>();
+
+        self.world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.enable_component_events::<C>(world);
+            });
+
+        self
+    }
+}
+
+/// An event that indicates that component `C` was inserted or mutated by the server.
+///
+/// Not sent if the write function didn't change the component.
+/// See [`AppComponentEventExt::add_component_events`] for details.
+///
+/// Enabled via [`AppComponentEventExt::add_component_events`].
+#[derive(Event)]
+pub struct ComponentReplicated<C: Component> {
+    /// Client entity for which the component was received.
+    pub entity: Entity,
+
+    /// Tick of the message from which the component was received.
+    pub tick: RepliconTick,
+
+    /// Whether the component was inserted or mutated.
+    ///
+    /// Determined right before the write is applied.
+    pub kind: ReplicationKind,
+
+    marker: PhantomData<C>,
+}
+
+/// An event that indicates that component `C` was removed by the server.
+///
+/// Not sent if the component was already missing on the entity.
+///
+/// Enabled via [`AppComponentEventExt::add_component_events`].
+#[derive(Event)]
+pub struct ComponentRemovedByServer<C: Component> {
+    /// Client entity from which the component was removed.
+    pub entity: Entity,
+
+    /// Tick of the message with the removal.
+    pub tick: RepliconTick,
+
+    marker: PhantomData<C>,
+}
+
+/// Kind of a received component change.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReplicationKind {
+    /// The component wasn't present on the entity before.
+    Insertion,
+    /// The component was already present on the entity.
+    Mutation,
+}
+
+/// Functions that send typed component events.
+#[derive(Clone, Copy)]
+pub(crate) struct ComponentEventFns {
+    replicated: fn(&mut World, Entity, RepliconTick, ReplicationKind),
+    removed: fn(&mut World, Entity, RepliconTick),
+}
+
+impl ComponentEventFns {
+    pub(crate) fn new<C: Component>() -> Self {
+        Self {
+            replicated: send_replicated::<C>,
+            removed: send_removed::<C>,
+        }
+    }
+
+    /// Sends [`ComponentReplicated`] for the component for which this instance was created.
+    pub(crate) fn replicated(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        tick: RepliconTick,
+        kind: ReplicationKind,
+    ) {
+        (self.replicated)(world, entity, tick, kind);
+    }
+
+    /// Sends [`ComponentRemovedByServer`] for the component for which this instance was created.
+    pub(crate) fn removed(&self, world: &mut World, entity: Entity, tick: RepliconTick) {
+        (self.removed)(world, entity, tick);
+    }
+}
+
+fn send_replicated<C: Component>(
+    world: &mut World,
+    entity: Entity,
+    tick: RepliconTick,
+    kind: ReplicationKind,
+) {
+    world.send_event(ComponentReplicated::<C> {
+        entity,
+        tick,
+        kind,
+        marker: PhantomData,
+    });
+}
+
+fn send_removed<C: Component>(world: &mut World, entity: Entity, tick: RepliconTick) {
+    world.send_event(ComponentRemovedByServer::<C> {
+        entity,
+        tick,
+        marker: PhantomData,
+    });
+}
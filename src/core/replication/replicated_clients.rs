@@ -5,9 +5,10 @@ use std::mem;
 use bevy::{
     ecs::{component::Tick, entity::EntityHashMap},
     prelude::*,
-    utils::{Duration, HashMap},
+    utils::{Duration, HashMap, HashSet},
 };
 
+use super::replication_scope::ReplicationScope;
 use crate::core::{replicon_tick::RepliconTick, ClientId};
 
 use client_visibility::ClientVisibility;
@@ -105,6 +106,16 @@ impl ReplicatedClients {
         self.clients.iter_mut()
     }
 
+    /// Returns mutable chunks of connected clients with the specified size.
+    ///
+    /// The last chunk may be shorter.
+    pub(crate) fn chunks_mut(
+        &mut self,
+        chunk_size: usize,
+    ) -> impl Iterator<Item = &mut [ReplicatedClient]> {
+        self.clients.chunks_mut(chunk_size)
+    }
+
     /// Returns the number of connected clients.
     pub fn len(&self) -> usize {
         self.clients.len()
@@ -191,6 +202,21 @@ pub struct ReplicatedClient {
     ///
     /// See also [`Self::register_mutate_message`].
     next_mutate_index: u16,
+
+    /// Indicates that all visible entities should be sent as new in the next update message.
+    ///
+    /// See also [`Self::request_resync`].
+    resync: bool,
+
+    /// Scopes of entities that will be replicated to this client.
+    ///
+    /// See also [`ReplicationScope`].
+    scopes: HashSet<ReplicationScope>,
+
+    /// Indicates that scopes were removed since the last tick.
+    ///
+    /// Used to despawn entities from removed scopes on the client.
+    scopes_removed: bool,
 }
 
 impl ReplicatedClient {
@@ -202,6 +228,9 @@ impl ReplicatedClient {
             update_tick: Default::default(),
             mutations: Default::default(),
             next_mutate_index: Default::default(),
+            resync: false,
+            scopes: Default::default(),
+            scopes_removed: false,
         }
     }
 
@@ -220,6 +249,72 @@ impl ReplicatedClient {
         &mut self.visibility
     }
 
+    /// Assigns the client to a scope.
+    ///
+    /// Entities from this scope will be sent as new in the next update message.
+    /// See also [`ReplicationScope`].
+    pub fn add_scope(&mut self, scope: ReplicationScope) {
+        self.scopes.insert(scope);
+    }
+
+    /// Removes the client from a scope.
+    ///
+    /// Entities from this scope will be despawned on the client.
+    /// See also [`ReplicationScope`].
+    pub fn remove_scope(&mut self, scope: ReplicationScope) {
+        if self.scopes.remove(&scope) {
+            self.scopes_removed = true;
+        }
+    }
+
+    /// Returns `true` if the client is assigned to a scope.
+    pub fn in_scope(&self, scope: ReplicationScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    /// Returns an iterator over all scopes of the client.
+    pub fn scopes(&self) -> impl Iterator<Item = ReplicationScope> + '_ {
+        self.scopes.iter().copied()
+    }
+
+    /// Removes entities that were replicated to the client, but no longer in its scopes.
+    ///
+    /// If any scope was removed from the client, checks all its entities.
+    /// Otherwise checks only `changed` entities whose scope was changed.
+    /// Removed entities are pushed into `lost`.
+    pub(crate) fn remove_out_of_scope(
+        &mut self,
+        world: &World,
+        changed: &[Entity],
+        lost: &mut Vec<Entity>,
+    ) {
+        let scopes = &self.scopes;
+        let out_of_scope = |entity: Entity| {
+            world
+                .get::<ReplicationScope>(entity)
+                .is_some_and(|scope| !scopes.contains(scope))
+        };
+
+        if self.scopes_removed {
+            self.scopes_removed = false;
+            self.mutation_ticks.retain(|&entity, _| {
+                if out_of_scope(entity) {
+                    lost.push(entity);
+                    false
+                } else {
+                    true
+                }
+            });
+        } else {
+            for &entity in changed {
+                if self.mutation_ticks.contains_key(&entity) && out_of_scope(entity) {
+                    self.mutation_ticks.remove(&entity);
+                    lost.push(entity);
+                }
+            }
+        }
+    }
+
     /// Sets the client's update tick.
     pub(crate) fn set_update_tick(&mut self, tick: RepliconTick) {
         self.update_tick = tick;
@@ -249,6 +344,31 @@ impl ReplicatedClient {
         self.mutation_ticks.clear();
         self.mutations.clear();
         self.next_mutate_index = 0;
+        self.resync = false;
+        self.scopes.clear();
+        self.scopes_removed = false;
+    }
+
+    /// Forgets all replicated state for the client and resends all visible entities in the next update message.
+    ///
+    /// The client will apply the message as a replacement of its replicated state.
+    /// Called automatically when the client sends [`ResyncRequest`](crate::core::replication::resync_request::ResyncRequest).
+    ///
+    /// Keeps allocated memory in the buffers for reuse.
+    pub(crate) fn request_resync(&mut self, client_buffers: &mut ClientBuffers) {
+        client_buffers.entities.extend(self.drain_entities());
+        self.mutation_ticks.clear();
+        self.resync = true;
+    }
+
+    /// Returns `true` if the resync was requested, but not sent yet.
+    pub(crate) fn resync_pending(&self) -> bool {
+        self.resync
+    }
+
+    /// Marks the requested resync as sent.
+    pub(crate) fn finish_resync(&mut self) {
+        self.resync = false;
     }
 
     /// Registers mutate message at specified `tick` and `timestamp` and returns its index with entities to fill.
@@ -352,12 +472,15 @@ impl ReplicatedClient {
 
     /// Removes all mutate messages older then `min_timestamp`.
     ///
+    /// Returns the number of removed messages.
+    ///
     /// Keeps allocated memory in the buffers for reuse.
     pub(crate) fn cleanup_older_mutations(
         &mut self,
         client_buffers: &mut ClientBuffers,
         min_timestamp: Duration,
-    ) {
+    ) -> usize {
+        let len = self.mutations.len();
         self.mutations.retain(|_, mutate_info| {
             if mutate_info.timestamp < min_timestamp {
                 client_buffers
@@ -368,6 +491,13 @@ impl ReplicatedClient {
                 true
             }
         });
+
+        len - self.mutations.len()
+    }
+
+    /// Returns the number of sent mutate messages that weren't acknowledged yet.
+    pub fn pending_mutations(&self) -> usize {
+        self.mutations.len()
     }
 }
 
@@ -96,7 +96,7 @@ impl ClientVisibility {
     /// Removes a despawned entity tracked by this client.
     pub(super) fn remove_despawned(&mut self, entity: Entity) {
         match &mut self.filter {
-            VisibilityFilter::All { .. } => (),
+            VisibilityFilter::All => (),
             VisibilityFilter::Blacklist {
                 list,
                 added,
@@ -123,7 +123,7 @@ impl ClientVisibility {
     /// Drains all entities for which visibility was lost during this tick.
     pub(super) fn drain_lost(&mut self) -> impl Iterator<Item = Entity> + '_ {
         match &mut self.filter {
-            VisibilityFilter::All { .. } => VisibilityLostIter::AllVisible,
+            VisibilityFilter::All => VisibilityLostIter::AllVisible,
             VisibilityFilter::Blacklist { added, .. } => VisibilityLostIter::Lost(added.drain()),
             VisibilityFilter::Whitelist { removed, .. } => {
                 VisibilityLostIter::Lost(removed.drain())
@@ -136,7 +136,7 @@ impl ClientVisibility {
     /// Does nothing if the visibility policy for the server plugin is set to [`VisibilityPolicy::All`].
     pub fn set_visibility(&mut self, entity: Entity, visible: bool) {
         match &mut self.filter {
-            VisibilityFilter::All { .. } => {
+            VisibilityFilter::All => {
                 if visible {
                     debug!(
                         "ignoring visibility enable due to {:?}",
@@ -7,15 +7,27 @@ pub mod test_fns;
 use bevy::{ecs::component::ComponentId, prelude::*};
 use serde::{Deserialize, Serialize};
 
-use super::command_markers::CommandMarkerIndex;
+use super::{command_markers::CommandMarkerIndex, component_events::ComponentEventFns};
+use crate::core::server_entity_map::ServerEntityMap;
 use command_fns::{RemoveFn, UntypedCommandFns, WriteFn};
 use component_fns::ComponentFns;
-use ctx::DespawnCtx;
+use ctx::{DespawnCtx, SpawnCtx};
 use rule_fns::{RuleFns, UntypedRuleFns};
 
 /// Stores configurable replication functions.
 #[derive(Resource)]
 pub struct ReplicationRegistry {
+    /// Custom function to handle entity spawning on client.
+    ///
+    /// By default uses [`spawn_empty`].
+    /// Useful if you need to insert components up front, spawn entities into a specific parent
+    /// or reuse entities from a pool. [`Replicated`](super::Replicated) will be inserted
+    /// into the returned entity automatically. The returned entity shouldn't be mapped to another
+    /// server entity, otherwise a new empty entity will be spawned instead.
+    ///
+    /// Also used for entities that are first referenced inside mapped components.
+    pub spawn: SpawnFn,
+
     /// Custom function to handle entity despawning.
     ///
     /// By default uses [`despawn_recursive`].
@@ -97,6 +109,15 @@ impl ReplicationRegistry {
         }
     }
 
+    /// Enables typed replication events for a component.
+    ///
+    /// See also [`AppComponentEventExt::add_component_events`](super::component_events::AppComponentEventExt::add_component_events).
+    pub(super) fn enable_component_events<C: Component>(&mut self, world: &mut World) {
+        let (index, _) = self.init_component_fns::<C>(world);
+        let (_, component_fns) = &mut self.components[index];
+        component_fns.set_event_fns(ComponentEventFns::new::<C>());
+    }
+
     /// Registers serialization/deserialization functions for a component.
     ///
     /// Returned data can be assigned to a
@@ -131,6 +152,15 @@ impl ReplicationRegistry {
         (index, component_id)
     }
 
+    /// Returns IDs of all registered components with their functions.
+    pub(crate) fn iter_component_fns(
+        &self,
+    ) -> impl Iterator<Item = (ComponentId, &ComponentFns)> + '_ {
+        self.components
+            .iter()
+            .map(|(component_id, component_fns)| (*component_id, component_fns))
+    }
+
     /// Returns associates functions.
     ///
     /// See also [`Self::register_rule_fns`].
@@ -145,11 +175,29 @@ impl ReplicationRegistry {
 
         (*component_id, command_fns, rule_fns)
     }
+
+    /// Like [`Self::get`], but returns an error instead of panicking if the ID is unknown.
+    ///
+    /// Should be used for IDs received over the network.
+    pub(crate) fn try_get(
+        &self,
+        fns_id: FnsId,
+    ) -> bincode::Result<(ComponentId, &ComponentFns, &UntypedRuleFns)> {
+        if fns_id.0 >= self.rules.len() {
+            return Err(Box::new(bincode::ErrorKind::Custom(format!(
+                "function ID {} is not registered",
+                fns_id.0
+            ))));
+        }
+
+        Ok(self.get(fns_id))
+    }
 }
 
 impl Default for ReplicationRegistry {
     fn default() -> Self {
         Self {
+            spawn: spawn_empty,
             despawn: despawn_recursive,
             components: Default::default(),
             rules: Default::default(),
@@ -164,6 +212,42 @@ impl Default for ReplicationRegistry {
 #[derive(Clone, Copy, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct FnsId(usize);
 
+/// Signature of the entity spawn function.
+///
+/// Should return the spawned (or reused) client entity for the server entity from the context.
+///
+/// Called during deserialization for entities referenced inside mapped components,
+/// so only [`Commands`] are available.
+pub type SpawnFn = fn(&SpawnCtx, &mut Commands) -> Entity;
+
+/// Default entity spawn function.
+pub fn spawn_empty(_ctx: &SpawnCtx, commands: &mut Commands) -> Entity {
+    commands.spawn_empty().id()
+}
+
+/// Spawns a client entity for a server entity using `spawn` and inserts [`Replicated`](super::Replicated).
+///
+/// Falls back to a new empty entity if the returned entity is already mapped to another server entity.
+pub(crate) fn spawn_replicated(
+    spawn: SpawnFn,
+    ctx: &SpawnCtx,
+    commands: &mut Commands,
+    entity_map: &ServerEntityMap,
+) -> Entity {
+    let mut client_entity = (spawn)(ctx, commands);
+    if let Some(mapped_entity) = entity_map.to_server().get(&client_entity) {
+        error!(
+            "spawn function returned `{client_entity:?}` for server's `{:?}`, \
+            but it's already mapped to server's `{mapped_entity:?}`, spawning a new entity instead",
+            ctx.server_entity
+        );
+        client_entity = commands.spawn_empty().id();
+    }
+    commands.entity(client_entity).insert(super::Replicated);
+
+    client_entity
+}
+
 /// Signature of the entity despawn function.
 pub type DespawnFn = fn(&DespawnCtx, EntityWorldMut);
 
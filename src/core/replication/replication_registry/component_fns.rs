@@ -1,6 +1,6 @@
 use std::io::Cursor;
 
-use bevy::{prelude::*, ptr::Ptr};
+use bevy::{ecs::world::CommandQueue, prelude::*, ptr::Ptr};
 
 use super::{
     command_fns::UntypedCommandFns,
@@ -9,6 +9,7 @@ use super::{
 };
 use crate::core::replication::{
     command_markers::{CommandMarkerIndex, CommandMarkers, EntityMarkers},
+    component_events::{ComponentEventFns, ReplicationKind},
     deferred_entity::DeferredEntity,
 };
 
@@ -21,6 +22,7 @@ pub(crate) struct ComponentFns {
     consume: UntypedConsumeFn,
     commands: UntypedCommandFns,
     markers: Vec<Option<UntypedCommandFns>>,
+    events: Option<ComponentEventFns>,
 }
 
 impl ComponentFns {
@@ -32,6 +34,7 @@ impl ComponentFns {
             consume: untyped_consume::<C>,
             commands: UntypedCommandFns::default_fns::<C>(),
             markers: vec![None; marker_slots],
+            events: None,
         }
     }
 
@@ -78,6 +81,13 @@ impl ComponentFns {
         self.commands = command_fns;
     }
 
+    /// Enables typed replication events.
+    ///
+    /// `event_fns` should be created for the same type as this instance.
+    pub(super) fn set_event_fns(&mut self, event_fns: ComponentEventFns) {
+        self.events = Some(event_fns);
+    }
+
     /// Restores erased type from `ptr` and `rule_fns` to the type for which this instance was created.
     ///
     /// # Safety
@@ -118,7 +128,7 @@ impl ComponentFns {
             .find_map(|(&fns, _)| fns)
             .unwrap_or(self.commands);
 
-        (self.write)(ctx, &command_fns, rule_fns, entity, cursor)
+        self.write_and_notify(ctx, &command_fns, rule_fns, entity, cursor)
     }
 
     /// Calls the assigned writing or consuming function based on entity markers.
@@ -147,7 +157,7 @@ impl ComponentFns {
             .find_map(|((&fns, _), need_history)| fns.map(|fns| (fns, need_history)))
             .and_then(|(fns, need_history)| need_history.then_some(fns))
         {
-            (self.write)(ctx, &command_fns, rule_fns, entity, cursor)
+            self.write_and_notify(ctx, &command_fns, rule_fns, entity, cursor)
         } else {
             (self.consume)(ctx, rule_fns, cursor)
         }
@@ -168,7 +178,110 @@ impl ComponentFns {
             .find_map(|(&fns, _)| fns)
             .unwrap_or(self.commands);
 
-        command_fns.remove(ctx, entity)
+        let Some(events) = self.events else {
+            command_fns.remove(ctx, entity);
+            return;
+        };
+
+        // Collect commands from the remove function to check if the component was actually removed.
+        let mut queue = CommandQueue::default();
+        let mut commands = entity.commands(&mut queue);
+        let mut remove_ctx = RemoveCtx {
+            commands: &mut commands,
+            component_id: ctx.component_id,
+            message_tick: ctx.message_tick,
+        };
+        command_fns.remove(&mut remove_ctx, entity);
+
+        let entity = entity.id();
+        let component_id = ctx.component_id;
+        let tick = ctx.message_tick;
+        ctx.commands.queue(move |world: &mut World| {
+            let contains = |world: &World| {
+                world
+                    .get_entity(entity)
+                    .is_ok_and(|entity| entity.contains_id(component_id))
+            };
+            let contained = contains(world);
+            queue.apply(world);
+            if contained && !contains(world) {
+                events.removed(world, entity, tick);
+            }
+        });
+    }
+
+    /// Calls the write function and sends the replication event if enabled.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `rule_fns` was created for the same type as this instance.
+    unsafe fn write_and_notify(
+        &self,
+        ctx: &mut WriteCtx,
+        command_fns: &UntypedCommandFns,
+        rule_fns: &UntypedRuleFns,
+        entity: &mut DeferredEntity,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> bincode::Result<()> {
+        let Some(events) = self.events else {
+            return (self.write)(ctx, command_fns, rule_fns, entity, cursor);
+        };
+
+        // Collect commands from the write function to determine the kind right before they are applied,
+        // since previously queued commands could insert or remove the component.
+        let mut queue = CommandQueue::default();
+        let mut commands = entity.commands(&mut queue);
+        // Write functions could discard the value (e.g. store it in a history), so the event is sent
+        // only if the component was actually inserted or changed.
+        let this_run = entity.world().read_change_tick();
+        let changed_before = entity
+            .get_change_ticks_by_id(ctx.component_id)
+            .map(|ticks| ticks.changed);
+        let mut write_ctx = WriteCtx {
+            commands: &mut commands,
+            entity_map: ctx.entity_map,
+            component_id: ctx.component_id,
+            message_tick: ctx.message_tick,
+            ignore_mapping: ctx.ignore_mapping,
+            spawn: ctx.spawn,
+            spawned: ctx.spawned,
+        };
+        let result = (self.write)(&mut write_ctx, command_fns, rule_fns, entity, cursor);
+        if result.is_err() {
+            ctx.commands.append(&mut queue);
+            return result;
+        }
+
+        let entity = entity.id();
+        let component_id = ctx.component_id;
+        let tick = ctx.message_tick;
+        ctx.commands.queue(move |world: &mut World| {
+            let kind = if world
+                .get_entity(entity)
+                .is_ok_and(|entity| entity.contains_id(component_id))
+            {
+                ReplicationKind::Mutation
+            } else {
+                ReplicationKind::Insertion
+            };
+            queue.apply(world);
+            let changed_after = world
+                .get_entity(entity)
+                .ok()
+                .and_then(|entity| entity.get_change_ticks_by_id(component_id))
+                .map(|ticks| ticks.changed);
+            let written = match (changed_before, changed_after) {
+                (_, None) => false,
+                (None, Some(_)) => true,
+                // Writes can't be distinguished if the component was already changed during this tick.
+                (Some(before), Some(after)) => before == this_run || after != before,
+            };
+            if written {
+                events.replicated(world, entity, tick, kind);
+            }
+        });
+
+        Ok(())
     }
 }
 
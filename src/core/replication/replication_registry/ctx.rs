@@ -1,8 +1,7 @@
 use bevy::{ecs::component::ComponentId, prelude::*};
 
-use crate::core::{
-    replication::Replicated, replicon_tick::RepliconTick, server_entity_map::ServerEntityMap,
-};
+use super::SpawnFn;
+use crate::core::{replicon_tick::RepliconTick, server_entity_map::ServerEntityMap};
 
 /// Replication context for serialization function.
 #[non_exhaustive]
@@ -31,6 +30,14 @@ pub struct WriteCtx<'a, 'w, 's> {
 
     /// Disables mapping logic to avoid spawning entities for consume functions.
     pub(super) ignore_mapping: bool,
+
+    /// Function to spawn entities for unknown mapped server entities.
+    pub(super) spawn: SpawnFn,
+
+    /// Server entities spawned during mapping.
+    ///
+    /// Used to revert them if the message turns out to be malformed.
+    pub(super) spawned: &'a mut Vec<Entity>,
 }
 
 impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
@@ -39,6 +46,8 @@ impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
         entity_map: &'a mut ServerEntityMap,
         component_id: ComponentId,
         message_tick: RepliconTick,
+        spawn: SpawnFn,
+        spawned: &'a mut Vec<Entity>,
     ) -> Self {
         Self {
             commands,
@@ -46,6 +55,8 @@ impl<'a, 'w, 's> WriteCtx<'a, 'w, 's> {
             component_id,
             message_tick,
             ignore_mapping: false,
+            spawn,
+            spawned,
         }
     }
 }
@@ -56,8 +67,20 @@ impl EntityMapper for WriteCtx<'_, '_, '_> {
             return entity;
         }
 
-        self.entity_map
-            .get_by_server_or_insert(entity, || self.commands.spawn(Replicated).id())
+        if let Some(client_entity) = self.entity_map.get_by_server_or_restore(entity) {
+            return client_entity;
+        }
+
+        let ctx = SpawnCtx {
+            server_entity: entity,
+            message_tick: self.message_tick,
+        };
+        let client_entity =
+            super::spawn_replicated(self.spawn, &ctx, self.commands, self.entity_map);
+        self.entity_map.insert(entity, client_entity);
+        self.spawned.push(entity);
+
+        client_entity
     }
 }
 
@@ -74,6 +97,16 @@ pub struct RemoveCtx<'a, 'w, 's> {
     pub message_tick: RepliconTick,
 }
 
+/// Replication context for spawn.
+#[non_exhaustive]
+pub struct SpawnCtx {
+    /// Entity on the server for which the client entity is spawned.
+    pub server_entity: Entity,
+
+    /// Tick for the currently processing message.
+    pub message_tick: RepliconTick,
+}
+
 /// Replication context for despawn.
 #[non_exhaustive]
 pub struct DespawnCtx {
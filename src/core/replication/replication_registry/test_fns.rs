@@ -130,8 +130,15 @@ impl TestFnsEntityExt for EntityWorldMut<'_> {
 
                     let (component_id, component_fns, rule_fns) = registry.get(fns_id);
                     let mut cursor = Cursor::new(data);
-                    let mut ctx =
-                        WriteCtx::new(&mut commands, &mut entity_map, component_id, message_tick);
+                    let mut spawned = Vec::new();
+                    let mut ctx = WriteCtx::new(
+                        &mut commands,
+                        &mut entity_map,
+                        component_id,
+                        message_tick,
+                        registry.spawn,
+                        &mut spawned,
+                    );
 
                     unsafe {
                         component_fns
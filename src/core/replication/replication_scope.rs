@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+/// Assigns a replicated entity to a scope on the server.
+///
+/// Scoped entities are replicated only to clients assigned to the same scope via
+/// [`ReplicatedClient::add_scope`](super::replicated_clients::ReplicatedClient::add_scope).
+/// Entities without this component are replicated to all clients.
+///
+/// Unlike [`ClientVisibility`](super::replicated_clients::client_visibility::ClientVisibility),
+/// scopes don't require per-entity bookkeeping for each client: entities outside of a client's scopes
+/// are skipped entirely during the collection of changes. This makes them suitable for running multiple
+/// independent simulations, like matches, in a single server. Visibility is still applied within a scope.
+///
+/// Changing the component value moves the entity into another scope: it will be despawned on clients
+/// that lost access to it and sent as new to clients that gained it.
+///
+/// The component is not replicated.
+#[derive(Component, Deref, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct ReplicationScope(pub u32);
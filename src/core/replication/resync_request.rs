@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A client event that requests the server to resend the whole replicated state.
+///
+/// Useful when the client detects that its world has drifted from the server, for example after
+/// a failed desync check. Registered automatically, just send it with [`EventWriter`] on the client.
+///
+/// The server resends all visible entities as fresh insertions in the next update message.
+/// The client applies it as a replacement: replicated components that aren't present in the message
+/// are removed and replicated entities that aren't present in the message are despawned.
+#[derive(Event, Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct ResyncRequest;
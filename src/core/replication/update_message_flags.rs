@@ -7,17 +7,46 @@ bitflags! {
     #[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
     pub(crate) struct UpdateMessageFlags: u8 {
         const MAPPINGS = 0b00000001;
-        const DESPAWNS = 0b00000010;
-        const REMOVALS = 0b00000100;
-        const CHANGES = 0b00001000;
+        const UNMAPPED = 0b00000010;
+        const DESPAWNS = 0b00000100;
+        const REMOVALS = 0b00001000;
+        const CHANGES = 0b00010000;
+        /// The message contains only the reason passed to
+        /// [`RepliconServer::disconnect`](crate::core::replicon_server::RepliconServer::disconnect)
+        /// as UTF-8 after the flags.
+        ///
+        /// Can't be combined with other flags.
+        const DISCONNECT = 0b00100000;
+        /// The message contains the whole replicated state and should replace the existing one.
+        ///
+        /// Doesn't correspond to any data, use [`Self::data`] to exclude it.
+        const RESYNC = 0b01000000;
+        /// Message tick is serialized as varint instead of fixint.
+        ///
+        /// Doesn't correspond to any data, use [`Self::data`] to exclude it.
+        const VARINT_TICK = 0b10000000;
     }
 }
 
 impl UpdateMessageFlags {
-    /// Returns the last set flag in the message.
+    /// Returns only flags that correspond to the included data.
+    pub(crate) fn data(self) -> UpdateMessageFlags {
+        self.difference(
+            UpdateMessageFlags::VARINT_TICK
+                | UpdateMessageFlags::RESYNC
+                | UpdateMessageFlags::DISCONNECT,
+        )
+    }
+
+    /// Returns the last set data flag in the message.
+    ///
+    /// Returns empty flags if the message doesn't contain any data.
     pub(crate) fn last(self) -> UpdateMessageFlags {
-        debug_assert!(!self.is_empty());
-        let zeroes = u8::BITS - 1 - self.bits().leading_zeros();
+        let data = self.data();
+        if data.is_empty() {
+            return data;
+        }
+        let zeroes = u8::BITS - 1 - data.bits().leading_zeros();
         UpdateMessageFlags::from_bits_retain(1 << zeroes)
     }
 }
@@ -40,9 +69,25 @@ mod tests {
             UpdateMessageFlags::all().last(),
             UpdateMessageFlags::CHANGES
         );
+        assert_eq!(
+            (UpdateMessageFlags::MAPPINGS | UpdateMessageFlags::VARINT_TICK).last(),
+            UpdateMessageFlags::MAPPINGS
+        );
         assert_eq!(
             (UpdateMessageFlags::DESPAWNS | UpdateMessageFlags::REMOVALS).last(),
             UpdateMessageFlags::REMOVALS
         );
+        assert_eq!(
+            (UpdateMessageFlags::MAPPINGS | UpdateMessageFlags::UNMAPPED).last(),
+            UpdateMessageFlags::UNMAPPED
+        );
+        assert_eq!(
+            (UpdateMessageFlags::CHANGES | UpdateMessageFlags::RESYNC).last(),
+            UpdateMessageFlags::CHANGES
+        );
+        assert_eq!(
+            UpdateMessageFlags::RESYNC.last(),
+            UpdateMessageFlags::empty()
+        );
     }
 }
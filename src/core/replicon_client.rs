@@ -1,7 +1,12 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
 use bytes::Bytes;
 
-use crate::core::ClientId;
+use crate::core::{
+    channels::ReplicationChannel, message_transform::MessageTransform,
+    replication::update_message_flags::UpdateMessageFlags, ClientId,
+};
 
 /// Stores information about a client independent from the messaging backend.
 ///
@@ -14,6 +19,11 @@ use crate::core::ClientId;
 /// - For sending messages, [`Self::drain_sent`] should be used to drain all sent messages.
 ///   A system to forward Replicon messages to the backend should run in
 ///   [`ClientSet::SendPackets`](crate::client::ClientSet::SendPackets).
+/// - For disconnecting, [`Self::take_disconnect_request`] should be checked after draining sent messages.
+///   If it returns `true`, the backend should close the connection.
+///
+/// If [`RepliconChannels::set_message_transform`](crate::core::channels::RepliconChannels::set_message_transform)
+/// was used, the transformation will be applied to messages in [`Self::drain_sent`] and reverted in [`Self::insert_received`].
 ///
 /// Inserted as resource by [`ClientPlugin`](crate::client::ClientPlugin).
 #[derive(Resource, Default)]
@@ -21,6 +31,21 @@ pub struct RepliconClient {
     /// Client connection status.
     status: RepliconClientStatus,
 
+    /// Reason of the last disconnect.
+    ///
+    /// See also [`Self::set_disconnected`].
+    disconnect_reason: Option<DisconnectReason>,
+
+    /// Reason received from the server over [`ReplicationChannel::Updates`].
+    ///
+    /// Becomes [`Self::disconnect_reason`] when the client disconnects.
+    server_reason: Option<String>,
+
+    /// Indicates that Replicon requested the backend to close the connection.
+    ///
+    /// See also [`Self::request_disconnect`].
+    disconnect_requested: bool,
+
     /// List of received messages for each channel.
     ///
     /// Top index is channel ID.
@@ -30,6 +55,12 @@ pub struct RepliconClient {
     /// List of sent messages and their channels since the last tick.
     sent_messages: Vec<(u8, Bytes)>,
 
+    /// Transformation for sent and received messages.
+    message_transform: Option<Arc<dyn MessageTransform>>,
+
+    /// Number of received messages that failed to decode.
+    decode_failures: usize,
+
     rtt: f64,
     packet_loss: f64,
     sent_bps: f64,
@@ -42,6 +73,11 @@ impl RepliconClient {
         self.received_messages.resize(channels_count, Vec::new());
     }
 
+    /// Sets the transformation for sent and received messages.
+    pub(crate) fn setup_message_transform(&mut self, transform: Option<Arc<dyn MessageTransform>>) {
+        self.message_transform = transform;
+    }
+
     /// Returns number of received messages for a channel.
     ///
     /// See also [`Self::receive`].
@@ -55,6 +91,20 @@ impl RepliconClient {
         channel_messages.len()
     }
 
+    /// Returns all received messages that weren't consumed yet with their channel IDs.
+    ///
+    /// Unlike [`Self::receive`], doesn't drain the messages.
+    pub fn iter_received(&self) -> impl Iterator<Item = (u8, &Bytes)> {
+        self.received_messages
+            .iter()
+            .enumerate()
+            .flat_map(|(channel_id, messages)| {
+                messages
+                    .iter()
+                    .map(move |message| (channel_id as u8, message))
+            })
+    }
+
     /// Receives all available messages from the server over a channel.
     ///
     /// All messages will be drained.
@@ -108,6 +158,10 @@ impl RepliconClient {
     /// Sets the client connection status.
     ///
     /// Discards all messages if the state changes from [`RepliconClientStatus::Connected`].
+    /// If the server sent a reason via [`RepliconServer::disconnect`](crate::core::replicon_server::RepliconServer::disconnect)
+    /// before that, it will be available via [`Self::disconnect_reason`] as [`DisconnectReason::Server`].
+    /// Clears [`Self::disconnect_reason`] and pending [`Self::request_disconnect`]
+    /// if the state changes from [`RepliconClientStatus::Disconnected`].
     /// See also [`Self::status`].
     ///
     /// <div class="warning">
@@ -123,6 +177,11 @@ impl RepliconClient {
                 channel_messages.clear();
             }
             self.sent_messages.clear();
+            if let Some(reason) = self.server_reason.take() {
+                if matches!(status, RepliconClientStatus::Disconnected) {
+                    self.disconnect_reason = Some(DisconnectReason::Server(reason));
+                }
+            }
 
             self.rtt = 0.0;
             self.packet_loss = 0.0;
@@ -130,9 +189,58 @@ impl RepliconClient {
             self.received_bps = 0.0;
         }
 
+        if self.is_disconnected() && !matches!(status, RepliconClientStatus::Disconnected) {
+            self.disconnect_reason = None;
+            self.disconnect_requested = false;
+        }
+
         self.status = status;
     }
 
+    /// Sets the client status to [`RepliconClientStatus::Disconnected`] and stores the reason.
+    ///
+    /// Behaves like [`Self::set_status`], but the reason will be available via [`Self::disconnect_reason`]
+    /// until the status changes again. A reason received from the server takes precedence over the passed one.
+    ///
+    /// <div class="warning">
+    ///
+    /// Should only be called from the messaging backend when the client disconnects.
+    ///
+    /// </div>
+    pub fn set_disconnected(&mut self, reason: DisconnectReason) {
+        let server_reason = self.server_reason.take();
+        self.set_status(RepliconClientStatus::Disconnected);
+        self.disconnect_reason = Some(server_reason.map_or(reason, DisconnectReason::Server));
+    }
+
+    /// Requests the messaging backend to close the connection.
+    ///
+    /// The backend should check the request via [`Self::take_disconnect_request`].
+    pub fn request_disconnect(&mut self) {
+        debug!("requesting disconnect from the server");
+        self.disconnect_requested = true;
+    }
+
+    /// Returns `true` if a disconnect was requested via [`Self::request_disconnect`] and resets the request.
+    ///
+    /// <div class="warning">
+    ///
+    /// Should only be called from the messaging backend.
+    ///
+    /// </div>
+    pub fn take_disconnect_request(&mut self) -> bool {
+        std::mem::take(&mut self.disconnect_requested)
+    }
+
+    /// Returns the reason of the last disconnect.
+    ///
+    /// Available only if the client is disconnected and the reason was provided using [`Self::set_disconnected`]
+    /// or received from the server.
+    #[inline]
+    pub fn disconnect_reason(&self) -> Option<&DisconnectReason> {
+        self.disconnect_reason.as_ref()
+    }
+
     /// Returns the current client status.
     ///
     /// See also [`Self::set_status`].
@@ -186,7 +294,24 @@ impl RepliconClient {
     ///
     /// </div>
     pub fn drain_sent(&mut self) -> impl Iterator<Item = (u8, Bytes)> + '_ {
-        self.sent_messages.drain(..)
+        let client_id = self.id();
+        let transform = self.message_transform.as_deref();
+        if transform.is_some() && client_id.is_none() && !self.sent_messages.is_empty() {
+            debug!(
+                "dropping {} message(s) that can't be encoded without a client ID",
+                self.sent_messages.len()
+            );
+            self.sent_messages.clear();
+        }
+
+        self.sent_messages
+            .drain(..)
+            .map(move |(channel_id, message)| match (transform, client_id) {
+                (Some(transform), Some(client_id)) => {
+                    (channel_id, transform.encode(client_id, channel_id, message))
+                }
+                _ => (channel_id, message),
+            })
     }
 
     /// Adds a message from the server to the list of received messages.
@@ -203,12 +328,61 @@ impl RepliconClient {
         }
 
         let channel_id = channel_id.into();
+        let mut message = message.into();
+        if let Some(transform) = &self.message_transform {
+            let Some(client_id) = self.id() else {
+                debug!("dropping message over channel {channel_id} that can't be decoded without a client ID");
+                self.decode_failures += 1;
+                return;
+            };
+            let Some(decoded) = transform.decode(client_id, channel_id, message) else {
+                debug!("dropping message over channel {channel_id} that failed to decode");
+                self.decode_failures += 1;
+                return;
+            };
+            message = decoded;
+        }
+
+        self.push_received(channel_id, message);
+    }
+
+    /// Like [`Self::insert_received`], but for already decoded messages.
+    ///
+    /// Skips the message transformation.
+    pub(crate) fn insert_decoded(&mut self, channel_id: u8, message: Bytes) {
+        if !self.is_connected() {
+            warn!("trying to insert a received message when the client is not connected");
+            return;
+        }
+
+        self.push_received(channel_id, message);
+    }
+
+    fn push_received(&mut self, channel_id: u8, message: Bytes) {
+        if channel_id == ReplicationChannel::Updates as u8
+            && message.first().is_some_and(|&flags| {
+                UpdateMessageFlags::from_bits_retain(flags).contains(UpdateMessageFlags::DISCONNECT)
+            })
+        {
+            let reason = String::from_utf8_lossy(&message[1..]).into_owned();
+            debug!("received disconnect reason \"{reason}\" from the server");
+            self.server_reason = Some(reason);
+            return;
+        }
+
         let channel_messages = self
             .received_messages
             .get_mut(channel_id as usize)
             .unwrap_or_else(|| panic!("client should have a channel with id {channel_id}"));
 
-        channel_messages.push(message.into());
+        channel_messages.push(message);
+    }
+
+    /// Returns the number of received messages that were dropped because they failed to decode.
+    ///
+    /// See also [`RepliconChannels::set_message_transform`](crate::core::channels::RepliconChannels::set_message_transform).
+    pub fn decode_failures(&self) -> usize {
+        self.decode_failures
     }
 
     /// Returns the round-time trip in seconds for the connection.
@@ -298,3 +472,24 @@ pub enum RepliconClientStatus {
     /// Needed only for users to access ID independent from messaging library.
     Connected { client_id: Option<ClientId> },
 }
+
+/// Reason why the [`RepliconClient`] was disconnected.
+///
+/// See also [`RepliconClient::set_disconnected`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// Disconnected by the client itself.
+    Requested,
+    /// Disconnected by the server.
+    ///
+    /// Stores the reason passed to [`RepliconServer::disconnect`](crate::core::replicon_server::RepliconServer::disconnect).
+    Server(String),
+    /// The connection timed out.
+    TimedOut,
+    /// Disconnected due to a malformed replication message.
+    ///
+    /// See also [`MalformedMessagePolicy::Disconnect`](crate::client::MalformedMessagePolicy::Disconnect).
+    MalformedMessage,
+    /// Backend-specific reason.
+    Other(String),
+}
@@ -1,7 +1,17 @@
-use bevy::prelude::*;
+use std::{cmp::Reverse, sync::Arc, time::Duration};
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 use bytes::Bytes;
 
-use crate::core::ClientId;
+use crate::core::{
+    channels::{ChannelKind, ReplicationChannel, RepliconChannels},
+    message_transform::MessageTransform,
+    replication::update_message_flags::UpdateMessageFlags,
+    ClientId,
+};
 
 /// Stores information about the server independent from the messaging backend.
 ///
@@ -11,6 +21,16 @@ use crate::core::ClientId;
 ///   A system to forward messages from the backend to Replicon should run in [`ServerSet::ReceivePackets`](crate::server::ServerSet::ReceivePackets).
 /// - For sending messages, [`Self::drain_sent`] should be used to drain all sent messages.
 ///   A system to forward messages from Replicon to the backend should run in [`ServerSet::SendPackets`](crate::server::ServerSet::SendPackets).
+/// - For disconnecting clients, [`Self::drain_disconnects`] should be used to drain all disconnect requests
+///   after draining sent messages. The backend should close the connection and trigger
+///   [`ServerEvent::ClientDisconnected`](crate::server::ServerEvent::ClientDisconnected) with the requested reason.
+///
+/// Sent messages are drained in order of their channel [`priority`](crate::core::channels::RepliconChannel::priority).
+/// If [`RepliconChannels::max_client_bps`] is set, messages that exceed the limit for a client
+/// are dropped for unreliable channels and postponed to the next frames for reliable channels.
+///
+/// If [`RepliconChannels::set_message_transform`] was used, the transformation will be applied
+/// to messages in [`Self::drain_sent`] and reverted in [`Self::insert_received`].
 ///
 /// Inserted as resource by [`ServerPlugin`](crate::server::ServerPlugin).
 #[derive(Resource, Default)]
@@ -28,6 +48,42 @@ pub struct RepliconServer {
 
     /// List of sent messages for each channel since the last tick.
     sent_messages: Vec<(ClientId, u8, Bytes)>,
+
+    /// Delivery guarantee and priority for each server channel.
+    ///
+    /// Top index is channel ID.
+    server_channels: Vec<(ChannelKind, u8)>,
+
+    /// Maximum number of bytes per second that can be sent to a single client.
+    ///
+    /// See also [`RepliconChannels::max_client_bps`].
+    max_client_bps: Option<usize>,
+
+    /// Maximum number of postponed bytes for a single client.
+    ///
+    /// See also [`RepliconChannels::max_client_postponed_bytes`].
+    max_client_postponed_bytes: usize,
+
+    /// Number of bytes that can be sent to each client at the moment.
+    client_budgets: HashMap<ClientId, f64>,
+
+    /// Reliable messages that exceeded the client budget.
+    ///
+    /// Will be sent before the new messages on the next frames.
+    postponed_messages: Vec<(ClientId, u8, Bytes)>,
+
+    /// Transformation for sent and received messages.
+    ///
+    /// See also [`RepliconChannels::set_message_transform`].
+    message_transform: Option<Arc<dyn MessageTransform>>,
+
+    /// Number of received messages that failed to decode.
+    decode_failures: usize,
+
+    /// Clients that should be disconnected by the backend with their reasons.
+    ///
+    /// See also [`Self::disconnect`].
+    disconnect_requests: Vec<(ClientId, String)>,
 }
 
 impl RepliconServer {
@@ -36,6 +92,18 @@ impl RepliconServer {
         self.received_messages.resize(channels_count, Vec::new());
     }
 
+    /// Stores priorities and delivery guarantees of server channels and the bandwidth limit.
+    pub(crate) fn setup_server_channels(&mut self, channels: &RepliconChannels) {
+        self.server_channels = channels
+            .server_channels()
+            .iter()
+            .map(|channel| (channel.kind, channel.priority))
+            .collect();
+        self.max_client_bps = channels.max_client_bps;
+        self.max_client_postponed_bytes = channels.max_client_postponed_bytes;
+        self.message_transform = channels.message_transform().cloned();
+    }
+
     /// Removes a disconnected client.
     pub(crate) fn remove_client(&mut self, client_id: ClientId) {
         for receive_channel in &mut self.received_messages {
@@ -43,6 +111,101 @@ impl RepliconServer {
         }
         self.sent_messages
             .retain(|&(sender_id, ..)| sender_id != client_id);
+        self.postponed_messages
+            .retain(|&(sender_id, ..)| sender_id != client_id);
+        self.client_budgets.remove(&client_id);
+        self.disconnect_requests
+            .retain(|&(sender_id, _)| sender_id != client_id);
+    }
+
+    /// Sorts sent messages by channel priority, keeping disconnect reasons last, and applies the bandwidth limit for each client.
+    ///
+    /// Client budgets are refilled based on `delta`, but can't accumulate more than one second of traffic.
+    /// A message is sent while the budget is positive, so the budget may become negative and will be repaid later.
+    /// Sizes are measured before the message transformation, which is applied in [`Self::drain_sent`].
+    /// Postponed messages from previous calls are processed first. If a reliable message is postponed,
+    /// all following messages for the same client and channel are postponed too to preserve the order.
+    /// Messages for clients that are about to be disconnected are never limited to flush them before disconnect.
+    /// If postponed messages for a client exceed [`RepliconChannels::max_client_postponed_bytes`],
+    /// they are dropped and a disconnect is requested.
+    pub(crate) fn limit_bandwidth(&mut self, delta: Duration) {
+        if !self.postponed_messages.is_empty() {
+            self.sent_messages
+                .splice(0..0, self.postponed_messages.drain(..));
+        }
+
+        let channels = &self.server_channels;
+        self.sent_messages.sort_by_key(|(_, channel_id, message)| {
+            let priority = channels
+                .get(*channel_id as usize)
+                .map(|&(_, priority)| priority)
+                .unwrap_or_default();
+            // Disconnect reason should be delivered after all other messages regardless of priority.
+            (is_disconnect_reason(*channel_id, message), Reverse(priority))
+        });
+
+        let Some(max_bps) = self.max_client_bps else {
+            return;
+        };
+
+        let max_bps = max_bps as f64;
+        let refill = max_bps * delta.as_secs_f64();
+        for budget in self.client_budgets.values_mut() {
+            *budget = (*budget + refill).min(max_bps);
+        }
+
+        let disconnect_requests = &self.disconnect_requests;
+        let mut blocked = HashSet::new();
+        let mut postponed_bytes = HashMap::<ClientId, usize>::new();
+        self.sent_messages.retain(|(client_id, channel_id, message)| {
+            if disconnect_requests
+                .iter()
+                .any(|(disconnect_id, _)| disconnect_id == client_id)
+            {
+                return true;
+            }
+
+            let budget = self.client_budgets.entry(*client_id).or_insert(max_bps);
+            if *budget > 0.0 && !blocked.contains(&(*client_id, *channel_id)) {
+                *budget -= message.len() as f64;
+                return true;
+            }
+
+            let kind = channels
+                .get(*channel_id as usize)
+                .map(|&(kind, _)| kind)
+                .unwrap_or(ChannelKind::Ordered);
+            if kind == ChannelKind::Unreliable {
+                trace!(
+                    "dropping {} bytes over channel {channel_id} for `{client_id:?}` due to bandwidth limit",
+                    message.len()
+                );
+            } else {
+                trace!(
+                    "postponing {} bytes over channel {channel_id} for `{client_id:?}` due to bandwidth limit",
+                    message.len()
+                );
+                blocked.insert((*client_id, *channel_id));
+                *postponed_bytes.entry(*client_id).or_default() += message.len();
+                self.postponed_messages
+                    .push((*client_id, *channel_id, message.clone()));
+            }
+
+            false
+        });
+
+        for (client_id, bytes) in postponed_bytes {
+            if bytes > self.max_client_postponed_bytes {
+                warn!(
+                    "disconnecting `{client_id:?}` with {bytes} postponed bytes that exceed the limit of {}",
+                    self.max_client_postponed_bytes
+                );
+                // The client is disconnected anyway, so there is no need to send the postponed data.
+                self.postponed_messages
+                    .retain(|&(postponed_id, ..)| postponed_id != client_id);
+                self.disconnect(client_id, "exceeded the limit of postponed messages");
+            }
+        }
     }
 
     /// Receives all available messages from clients over a channel.
@@ -77,6 +240,20 @@ impl RepliconServer {
         channel_messages.drain(..)
     }
 
+    /// Retains only received messages from a channel for which the predicate returns `true`.
+    pub(crate) fn retain_received(
+        &mut self,
+        channel_id: u8,
+        mut f: impl FnMut(ClientId, &Bytes) -> bool,
+    ) {
+        let channel_messages = self
+            .received_messages
+            .get_mut(channel_id as usize)
+            .unwrap_or_else(|| panic!("server should have a receive channel with id {channel_id}"));
+
+        channel_messages.retain(|(client_id, message)| f(*client_id, message));
+    }
+
     /// Sends a message to a client over a channel.
     ///
     /// <div class="warning">
@@ -118,6 +295,9 @@ impl RepliconServer {
                 receive_channel.clear();
             }
             self.sent_messages.clear();
+            self.postponed_messages.clear();
+            self.client_budgets.clear();
+            self.disconnect_requests.clear();
         }
 
         self.running = running;
@@ -129,14 +309,79 @@ impl RepliconServer {
         self.running
     }
 
-    /// Retains only the messages specified by the predicate.
+    /// Requests the messaging backend to disconnect a client with a reason.
+    ///
+    /// Messages sent to this client before the backend drains the request, including messages sent later
+    /// in the same frame, will be delivered first and won't be limited by [`RepliconChannels::max_client_bps`].
+    /// This allows sending a final event to the client before disconnecting it.
+    ///
+    /// The reason is sent to the client over [`ReplicationChannel::Updates`] and will be available
+    /// via [`RepliconClient::disconnect_reason`](crate::core::replicon_client::RepliconClient::disconnect_reason)
+    /// as [`DisconnectReason::Server`](crate::core::replicon_client::DisconnectReason::Server).
+    ///
+    /// If a disconnect for this client was already requested, the previous reason is kept.
+    /// See also [`Self::drain_disconnects`].
+    pub fn disconnect(&mut self, client_id: ClientId, reason: impl Into<String>) {
+        if !self.running {
+            warn!("trying to disconnect `{client_id:?}` when the server is not running");
+            return;
+        }
+
+        if self.is_disconnecting(client_id) {
+            debug!(
+                "ignoring disconnect request for `{client_id:?}` since it was already requested"
+            );
+            return;
+        }
+
+        let reason = reason.into();
+        debug!("requesting disconnect for `{client_id:?}` with reason \"{reason}\"");
+        let mut message = Vec::with_capacity(reason.len() + 1);
+        message.push(UpdateMessageFlags::DISCONNECT.bits());
+        message.extend_from_slice(reason.as_bytes());
+        self.send(client_id, ReplicationChannel::Updates, message);
+        self.disconnect_requests.push((client_id, reason));
+    }
+
+    /// Returns `true` if a disconnect was requested for a client and not drained by the backend yet.
+    ///
+    /// See also [`Self::disconnect`].
+    pub fn is_disconnecting(&self, client_id: ClientId) -> bool {
+        self.disconnect_requests
+            .iter()
+            .any(|&(disconnect_id, _)| disconnect_id == client_id)
+    }
+
+    /// Returns the number of received messages that were dropped because they failed to decode.
+    ///
+    /// See also [`RepliconChannels::set_message_transform`].
+    pub fn decode_failures(&self) -> usize {
+        self.decode_failures
+    }
+
+    /// Removes all sent messages for a client, returning them as an iterator with channel.
     ///
     /// Used for testing.
-    pub(crate) fn retain_sent<F>(&mut self, f: F)
-    where
-        F: FnMut(&(ClientId, u8, Bytes)) -> bool,
-    {
-        self.sent_messages.retain(f)
+    pub(crate) fn drain_sent_for(
+        &mut self,
+        client_id: ClientId,
+    ) -> impl Iterator<Item = (u8, Bytes)> + '_ {
+        let mut messages = Vec::new();
+        self.sent_messages
+            .retain(|(receiver_id, channel_id, message)| {
+                if *receiver_id == client_id {
+                    messages.push((*channel_id, message.clone()));
+                    false
+                } else {
+                    true
+                }
+            });
+
+        let transform = self.message_transform.as_deref();
+        messages.into_iter().map(move |(channel_id, message)| {
+            let message = encode(transform, client_id, channel_id, message);
+            (channel_id, message)
+        })
     }
 
     /// Removes all sent messages, returning them as an iterator with client ID and channel.
@@ -147,7 +392,39 @@ impl RepliconServer {
     ///
     /// </div>
     pub fn drain_sent(&mut self) -> impl Iterator<Item = (ClientId, u8, Bytes)> + '_ {
-        self.sent_messages.drain(..)
+        let transform = self.message_transform.as_deref();
+        self.sent_messages
+            .drain(..)
+            .map(move |(client_id, channel_id, message)| {
+                let message = encode(transform, client_id, channel_id, message);
+                (client_id, channel_id, message)
+            })
+    }
+
+    /// Removes all disconnect requests, returning them as an iterator with client ID and reason.
+    ///
+    /// Should be called after [`Self::drain_sent`] to deliver all messages before disconnecting.
+    /// See also [`Self::disconnect`].
+    ///
+    /// <div class="warning">
+    ///
+    /// Should only be called from the messaging backend.
+    ///
+    /// </div>
+    pub fn drain_disconnects(&mut self) -> impl Iterator<Item = (ClientId, String)> + '_ {
+        self.disconnect_requests.drain(..)
+    }
+
+    /// Removes the disconnect request for a client, returning its reason.
+    ///
+    /// Used for testing.
+    pub(crate) fn take_disconnect(&mut self, client_id: ClientId) -> Option<String> {
+        let index = self
+            .disconnect_requests
+            .iter()
+            .position(|&(disconnect_id, _)| disconnect_id == client_id)?;
+        let (_, reason) = self.disconnect_requests.remove(index);
+        Some(reason)
     }
 
     /// Adds a message from a client to the list of received messages.
@@ -174,6 +451,200 @@ impl RepliconServer {
             .get_mut(channel_id as usize)
             .unwrap_or_else(|| panic!("server should have a receive channel with id {channel_id}"));
 
-        receive_channel.push((client_id, message.into()));
+        let mut message = message.into();
+        if let Some(transform) = &self.message_transform {
+            let Some(decoded) = transform.decode(client_id, channel_id, message) else {
+                debug!("dropping message from `{client_id:?}` over channel {channel_id} that failed to decode");
+                self.decode_failures += 1;
+                return;
+            };
+            message = decoded;
+        }
+
+        receive_channel.push((client_id, message));
+    }
+}
+
+/// Applies the transform to a message if it's present.
+fn encode(
+    transform: Option<&dyn MessageTransform>,
+    client_id: ClientId,
+    channel_id: u8,
+    message: Bytes,
+) -> Bytes {
+    match transform {
+        Some(transform) => transform.encode(client_id, channel_id, message),
+        None => message,
     }
 }
+
+/// Returns `true` if the message was sent by [`RepliconServer::disconnect`].
+fn is_disconnect_reason(channel_id: u8, message: &[u8]) -> bool {
+    channel_id == ReplicationChannel::Updates as u8
+        && message
+            .first()
+            .is_some_and(|&flags| flags & UpdateMessageFlags::DISCONNECT.bits() != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::channels::RepliconChannel;
+
+    #[test]
+    fn priority() {
+        let mut channels = RepliconChannels::default();
+        let low = channels.create_server_channel(ChannelKind::Ordered.into());
+        let high = channels.create_server_channel(RepliconChannel {
+            priority: 2,
+            ..ChannelKind::Ordered.into()
+        });
+
+        let mut server = RepliconServer::default();
+        server.setup_server_channels(&channels);
+        server.set_running(true);
+        server.send(CLIENT_ID, low, vec![0]);
+        server.send(CLIENT_ID, high, vec![1]);
+        server.send(CLIENT_ID, low, vec![2]);
+
+        server.limit_bandwidth(Duration::ZERO);
+
+        let messages: Vec<_> = server.drain_sent().map(|(_, _, message)| message).collect();
+        assert_eq!(messages, [[1].as_slice(), &[0], &[2]]);
+    }
+
+    #[test]
+    fn bandwidth_limit() {
+        let mut channels = RepliconChannels::default();
+        let reliable = channels.create_server_channel(ChannelKind::Ordered.into());
+        let unreliable = channels.create_server_channel(ChannelKind::Unreliable.into());
+        channels.set_max_client_bps(Some(2));
+
+        let mut server = RepliconServer::default();
+        server.setup_server_channels(&channels);
+        server.set_running(true);
+        server.send(CLIENT_ID, reliable, vec![0, 0]);
+        server.send(CLIENT_ID, reliable, vec![1]);
+        server.send(CLIENT_ID, unreliable, vec![2]);
+
+        server.limit_bandwidth(Duration::ZERO);
+
+        let messages: Vec<_> = server.drain_sent().map(|(_, _, message)| message).collect();
+        assert_eq!(messages, [[0, 0].as_slice()]);
+
+        server.limit_bandwidth(Duration::from_millis(500));
+
+        let messages: Vec<_> = server.drain_sent().map(|(_, _, message)| message).collect();
+        assert_eq!(
+            messages,
+            [[1].as_slice()],
+            "reliable message should be postponed, but unreliable should be dropped"
+        );
+    }
+
+    #[test]
+    fn postponed_limit() {
+        let mut channels = RepliconChannels::default();
+        let reliable = channels.create_server_channel(ChannelKind::Ordered.into());
+        channels.set_max_client_bps(Some(1));
+        channels.set_max_client_postponed_bytes(2);
+
+        let mut server = RepliconServer::default();
+        server.setup_server_channels(&channels);
+        server.set_running(true);
+        server.send(CLIENT_ID, reliable, vec![0]);
+        server.send(CLIENT_ID, reliable, vec![1, 1]);
+
+        server.limit_bandwidth(Duration::ZERO);
+        assert!(!server.is_disconnecting(CLIENT_ID));
+        assert_eq!(server.drain_sent().count(), 1);
+
+        server.send(CLIENT_ID, reliable, vec![2]);
+
+        server.limit_bandwidth(Duration::ZERO);
+        assert!(
+            server.is_disconnecting(CLIENT_ID),
+            "client should be disconnected after exceeding the postponed limit"
+        );
+        let channels: Vec<_> = server
+            .drain_sent()
+            .map(|(_, channel_id, _)| channel_id)
+            .collect();
+        assert_eq!(
+            channels,
+            [ReplicationChannel::Updates as u8],
+            "only the disconnect reason should be sent"
+        );
+        assert!(server.postponed_messages.is_empty());
+    }
+
+    #[test]
+    fn disconnect_flush() {
+        let mut channels = RepliconChannels::default();
+        let reliable = channels.create_server_channel(ChannelKind::Ordered.into());
+        channels.set_max_client_bps(Some(1));
+
+        let mut server = RepliconServer::default();
+        server.setup_server_channels(&channels);
+        server.set_running(true);
+        server.send(CLIENT_ID, reliable, vec![0, 0]);
+        server.send(CLIENT_ID, reliable, vec![1]);
+        server.disconnect(CLIENT_ID, "kicked");
+        server.disconnect(CLIENT_ID, "ignored");
+        assert!(server.is_disconnecting(CLIENT_ID));
+
+        server.limit_bandwidth(Duration::ZERO);
+
+        let (reasons, messages): (Vec<_>, Vec<_>) = server
+            .drain_sent()
+            .partition(|&(_, channel_id, _)| channel_id == ReplicationChannel::Updates as u8);
+        let messages: Vec<_> = messages
+            .into_iter()
+            .map(|(_, _, message)| message)
+            .collect();
+        assert_eq!(
+            messages,
+            [[0, 0].as_slice(), &[1]],
+            "messages should be flushed before disconnect"
+        );
+        let reasons: Vec<_> = reasons
+            .into_iter()
+            .map(|(_, _, message)| message.slice(1..))
+            .collect();
+        assert_eq!(
+            reasons,
+            [b"kicked".as_slice()],
+            "reason should be sent once"
+        );
+
+        let disconnects: Vec<_> = server.drain_disconnects().collect();
+        assert_eq!(disconnects, [(CLIENT_ID, "kicked".to_string())]);
+        assert!(!server.is_disconnecting(CLIENT_ID));
+    }
+
+    #[test]
+    fn disconnect_after_low_priority() {
+        let mut channels = RepliconChannels::default();
+        let low = channels.create_server_channel(ChannelKind::Ordered.into());
+
+        let mut server = RepliconServer::default();
+        server.setup_server_channels(&channels);
+        server.set_running(true);
+        server.send(CLIENT_ID, low, vec![0]);
+        server.disconnect(CLIENT_ID, "kicked");
+
+        server.limit_bandwidth(Duration::ZERO);
+
+        let channels: Vec<_> = server
+            .drain_sent()
+            .map(|(_, channel_id, _)| channel_id)
+            .collect();
+        assert_eq!(
+            channels,
+            [low, ReplicationChannel::Updates as u8],
+            "reason should be sent after messages from lower priority channels"
+        );
+    }
+
+    const CLIENT_ID: ClientId = ClientId::new(1);
+}
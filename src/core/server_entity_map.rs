@@ -10,6 +10,12 @@ use bevy::{ecs::entity::EntityHashMap, prelude::*, utils::hashbrown::hash_map::E
 pub struct ServerEntityMap {
     server_to_client: EntityHashMap<Entity>,
     client_to_server: EntityHashMap<Entity>,
+
+    /// Server to client mappings from the previous connection.
+    ///
+    /// Restored on first access by server entity.
+    /// Used by [`ClientRepairPlugin`](crate::client::repair::ClientRepairPlugin).
+    stashed: EntityHashMap<Entity>,
 }
 
 impl ServerEntityMap {
@@ -52,7 +58,7 @@ impl ServerEntityMap {
         match self.server_to_client.entry(server_entity) {
             Entry::Occupied(entry) => *entry.get(),
             Entry::Vacant(entry) => {
-                let client_entity = (f)();
+                let client_entity = self.stashed.remove(&server_entity).unwrap_or_else(f);
                 entry.insert(client_entity);
                 self.client_to_server.insert(client_entity, server_entity);
                 client_entity
@@ -64,6 +70,19 @@ impl ServerEntityMap {
         self.server_to_client.get(&server_entity).copied()
     }
 
+    /// Like [`Self::get_by_server`], but restores the mapping from the stash if it's missing.
+    ///
+    /// See also [`Self::stash`].
+    pub(crate) fn get_by_server_or_restore(&mut self, server_entity: Entity) -> Option<Entity> {
+        if let Some(client_entity) = self.get_by_server(server_entity) {
+            return Some(client_entity);
+        }
+
+        let client_entity = self.stashed.remove(&server_entity)?;
+        self.insert(server_entity, client_entity);
+        Some(client_entity)
+    }
+
     pub(crate) fn remove_by_server(&mut self, server_entity: Entity) -> Option<Entity> {
         let client_entity = self.server_to_client.remove(&server_entity);
         if let Some(client_entity) = client_entity {
@@ -94,8 +113,23 @@ impl ServerEntityMap {
     }
 
     /// Clears the map.
+    ///
+    /// Stashed mappings are kept.
     pub fn clear(&mut self) {
         self.client_to_server.clear();
         self.server_to_client.clear();
     }
+
+    /// Moves all mappings into a stash that will be used to restore mappings on access by server entity.
+    ///
+    /// Useful to reuse client entities after reconnect since server entities stay the same.
+    pub(crate) fn stash(&mut self) {
+        self.client_to_server.clear();
+        self.stashed.extend(self.server_to_client.drain());
+    }
+
+    /// Takes all stashed mappings that weren't restored.
+    pub(crate) fn take_stashed(&mut self) -> EntityHashMap<Entity> {
+        std::mem::take(&mut self.stashed)
+    }
 }
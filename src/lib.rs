@@ -289,6 +289,9 @@ If you want to customize how the received component will be written or removed o
 on some marker component (for example, write into a different component), see [`AppMarkerExt`].
 Useful for implementing rollback and interpolation.
 
+To react only to component changes received from the server (and not to local writes),
+see [`AppComponentEventExt`].
+
 In order to serialize Bevy components you need to enable the `serialize` feature on Bevy.
 
 <div class="warning">
@@ -468,6 +471,11 @@ require access to the [`AppTypeRegistry`] resource.
 
 Don't forget to validate the contents of every [`Box<dyn Reflect>`] from a client, it could be anything!
 
+To protect the server from clients that flood it with events, configure [`ClientEventLimits`]
+for an event type or a specific client using the [`ClientEventLimiter`] resource.
+Events that exceed the limits are discarded before deserialization and reported via
+[`ClientEventLimitExceeded`].
+
 ### From server to client
 
 A similar technique is used to send events from server to clients. To do this,
@@ -526,6 +534,25 @@ Just like with components, all networked events should be registered in the same
 
 </div>
 
+### Requests
+
+For query-style interactions where a client needs an answer from the server,
+register a request with [`ClientRequestAppExt::add_client_request()`]. It creates
+a client event for the request and a server event for the response with automatically
+assigned [`RequestId`]s.
+
+Send requests using [`RequestSender`], answer [`FromClient<Request>`](Request) on
+server using [`FromClient::respond`] and read [`RequestOutcome`] on client. If the server
+doesn't answer within [`PendingRequests::timeout`], the outcome will contain no response.
+
+### Triggers
+
+Events can also be delivered to observers. Register them with
+[`ClientTriggerAppExt::add_client_trigger()`] or [`ServerTriggerAppExt::add_server_trigger()`]
+and send using [`ClientTriggerExt`] or [`ServerTriggerExt`] on [`Commands`].
+Received client triggers are observed as [`Trigger<FromClient<E>>`](Trigger), while
+server triggers are observed as [`Trigger<E>`](Trigger). Trigger targets are mapped automatically.
+
 ## Client visibility
 
 You can control which parts of the world are visible for each client by setting visibility policy
@@ -625,23 +652,38 @@ pub mod test_app;
 pub mod prelude {
     pub use super::{
         core::{
+            authority::{
+                Owned, Owner, OwnerDisconnectPolicy, OwnershipCommandsExt, OwnershipPlugin,
+                OwnershipTransferred,
+            },
             channels::{ChannelKind, RepliconChannel, RepliconChannels},
             common_conditions::*,
             connected_clients::ConnectedClients,
             event::{
                 client_event::{ClientEventAppExt, FromClient},
+                client_request::{
+                    ClientRequestAppExt, PendingRequests, Request, RequestId, RequestOutcome,
+                    RequestSender, Response,
+                },
+                client_trigger::{ClientTriggerAppExt, ClientTriggerExt},
                 server_event::{SendMode, ServerEventAppExt, ToClients},
+                server_trigger::{ServerTriggerAppExt, ServerTriggerExt},
             },
             replication::{
                 command_markers::AppMarkerExt,
+                component_events::{
+                    AppComponentEventExt, ComponentRemovedByServer, ComponentReplicated,
+                },
                 replicated_clients::{
                     client_visibility::ClientVisibility, ReplicatedClient, ReplicatedClients,
                     VisibilityPolicy,
                 },
                 replication_rules::AppRuleExt,
+                replication_scope::ReplicationScope,
+                resync_request::ResyncRequest,
                 Replicated,
             },
-            replicon_client::{RepliconClient, RepliconClientStatus},
+            replicon_client::{DisconnectReason, RepliconClient, RepliconClientStatus},
             replicon_server::RepliconServer,
             ClientId, RepliconCorePlugin,
         },
@@ -650,20 +692,37 @@ pub mod prelude {
 
     #[cfg(feature = "client")]
     pub use super::client::{
-        event::ClientEventPlugin, ClientPlugin, ClientReplicationStats, ClientSet,
+        event::ClientEventPlugin,
+        interpolation::{AppInterpolationExt, Interpolated, InterpolationPlugin, InterpolationSet},
+        pending_mapping::{MappingOutcome, PendingMapping, UnmappedEntityPolicy},
+        prediction::{
+            AppPredictionExt, Predicted, PredictionPlugin, PredictionSchedule, PredictionSet,
+            RollbackSkipped,
+        },
+        repair::ClientRepairPlugin,
+        replay::{ReplayPlayer, ReplayPlugin, ReplayRecorder},
+        server_clock::{ServerClock, ServerClockPlugin},
+        ClientPlugin, ClientReplicationStats, ClientSet, MalformedMessagePolicy, ReplicationBudget,
+        ReplicationError, ReplicationPaused,
     };
 
     #[cfg(feature = "server")]
     pub use super::server::{
         client_entity_map::{ClientEntityMap, ClientMapping},
         event::ServerEventPlugin,
-        ServerEvent, ServerPlugin, ServerSet, StartReplication, TickPolicy,
+        event_limits::{
+            ClientEventLimitExceeded, ClientEventLimiter, ClientEventLimits, LimitViolation,
+        },
+        ReplicatedClientStats, ServerEvent, ServerPlugin, ServerReplicationStats, ServerSet,
+        StartReplication, TickPolicy,
     };
 
     #[cfg(feature = "client_diagnostics")]
     pub use super::client::diagnostics::ClientDiagnosticsPlugin;
     #[cfg(feature = "parent_sync")]
     pub use super::parent_sync::{ParentSync, ParentSyncPlugin};
+    #[cfg(feature = "server_diagnostics")]
+    pub use super::server::diagnostics::ServerDiagnosticsPlugin;
 }
 
 pub use bincode;
@@ -681,6 +740,7 @@ use prelude::*;
 /// * [`ClientEventPlugin`] - with feature `client`.
 /// * [`ParentSyncPlugin`] - with feature `parent_sync`.
 /// * [`ClientDiagnosticsPlugin`] - with feature `client_diagnostics`.
+/// * [`ServerDiagnosticsPlugin`] - with feature `server_diagnostics`.
 pub struct RepliconPlugins;
 
 impl PluginGroup for RepliconPlugins {
@@ -695,7 +755,7 @@ impl PluginGroup for RepliconPlugins {
 
         #[cfg(feature = "client")]
         {
-            group = group.add(ClientPlugin).add(ClientEventPlugin);
+            group = group.add(ClientPlugin::default()).add(ClientEventPlugin);
         }
 
         #[cfg(feature = "parent_sync")]
@@ -708,6 +768,11 @@ impl PluginGroup for RepliconPlugins {
             group = group.add(ClientDiagnosticsPlugin);
         }
 
+        #[cfg(feature = "server_diagnostics")]
+        {
+            group = group.add(ServerDiagnosticsPlugin);
+        }
+
         group
     }
 }
@@ -1,12 +1,22 @@
 pub mod client_entity_map;
 pub(super) mod despawn_buffer;
+#[cfg(feature = "server_diagnostics")]
+pub mod diagnostics;
 pub mod event;
+pub mod event_limits;
 pub(super) mod removal_buffer;
 pub(super) mod replicated_archetypes;
 pub(super) mod replication_messages;
+pub(super) mod scope_buffer;
+pub(super) mod scope_clients;
 pub mod server_tick;
 
-use std::{io::Cursor, mem, ops::Range, time::Duration};
+use std::{
+    io::Cursor,
+    mem,
+    ops::{Add, Range},
+    time::Duration,
+};
 
 use bevy::{
     ecs::{
@@ -17,24 +27,30 @@ use bevy::{
     },
     prelude::*,
     ptr::Ptr,
+    tasks::ComputeTaskPool,
     time::common_conditions::on_timer,
+    utils::{self, HashMap},
 };
 
 use crate::core::{
     channels::{ReplicationChannel, RepliconChannels},
     common_conditions::{server_just_stopped, server_running},
     connected_clients::ConnectedClients,
-    event::server_event::BufferedServerEvents,
+    event::{client_event::FromClient, server_event::BufferedServerEvents},
     replication::{
         replicated_clients::{
-            client_visibility::Visibility, ClientBuffers, ReplicatedClients, VisibilityPolicy,
+            client_visibility::Visibility, ClientBuffers, ReplicatedClient, ReplicatedClients,
+            VisibilityPolicy,
         },
         replication_registry::{
             component_fns::ComponentFns, ctx::SerializeCtx, rule_fns::UntypedRuleFns,
             ReplicationRegistry,
         },
         replication_rules::ReplicationRules,
+        replication_scope::ReplicationScope,
+        resync_request::ResyncRequest,
         track_mutate_messages::TrackMutateMessages,
+        Replicated,
     },
     replicon_server::RepliconServer,
     replicon_tick::RepliconTick,
@@ -42,9 +58,15 @@ use crate::core::{
 };
 use client_entity_map::ClientEntityMap;
 use despawn_buffer::{DespawnBuffer, DespawnBufferPlugin};
+use event_limits::{ClientEventLimitExceeded, ClientEventLimiter};
 use removal_buffer::{RemovalBuffer, RemovalBufferPlugin};
 use replicated_archetypes::{ReplicatedArchetypes, ReplicatedComponent};
-use replication_messages::{serialized_data::SerializedData, ReplicationMessages};
+use replication_messages::{
+    mutate_message::MutateMessage, serialized_data::SerializedData, update_message::UpdateMessage,
+    ReplicationMessages,
+};
+use scope_buffer::{ScopeBuffer, ScopeBufferPlugin};
+use scope_clients::ScopeClients;
 use server_tick::ServerTick;
 
 pub struct ServerPlugin {
@@ -67,6 +89,15 @@ pub struct ServerPlugin {
     /// All events from server will be buffered on client until replication starts, except the ones marked as independent.
     /// See also [`ServerEventAppExt::make_independent`](crate::core::event::server_event::ServerEventAppExt::make_independent).
     pub replicate_after_connect: bool,
+
+    /// Maximum number of clients for which a single task collects component changes.
+    ///
+    /// Change collection is split by clients across [`ComputeTaskPool`]. Each task serializes
+    /// components into its own buffer, so data shared between clients in different tasks is serialized
+    /// multiple times. Increase this value to reduce duplicated serialization or decrease to use more threads.
+    ///
+    /// Should be greater than 0.
+    pub clients_per_task: usize,
 }
 
 impl Default for ServerPlugin {
@@ -76,6 +107,7 @@ impl Default for ServerPlugin {
             visibility_policy: Default::default(),
             mutations_timeout: Duration::from_secs(10),
             replicate_after_connect: true,
+            clients_per_task: 32,
         }
     }
 }
@@ -85,7 +117,7 @@ impl Default for ServerPlugin {
 /// Can be disabled for client-only apps.
 impl Plugin for ServerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((DespawnBufferPlugin, RemovalBufferPlugin))
+        app.add_plugins((DespawnBufferPlugin, RemovalBufferPlugin, ScopeBufferPlugin))
             .init_resource::<RepliconServer>()
             .init_resource::<ServerTick>()
             .init_resource::<ClientBuffers>()
@@ -96,6 +128,9 @@ impl Plugin for ServerPlugin {
                 self.replicate_after_connect,
             ))
             .init_resource::<BufferedServerEvents>()
+            .init_resource::<ClientEventLimiter>()
+            .add_event::<ClientEventLimitExceeded>()
+            .insert_resource(ClientsPerTask(self.clients_per_task.max(1)))
             .configure_sets(
                 PreUpdate,
                 (ServerSet::ReceivePackets, ServerSet::Receive).chain(),
@@ -123,15 +158,25 @@ impl Plugin for ServerPlugin {
                     .in_set(ServerSet::Receive)
                     .run_if(server_running),
             )
+            .add_systems(
+                PreUpdate,
+                Self::receive_resync_requests
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+            )
             .add_systems(
                 PostUpdate,
                 (
                     Self::send_replication
-                        .map(Result::unwrap)
+                        .map(utils::error)
                         .in_set(ServerSet::Send)
                         .run_if(server_running)
                         .run_if(resource_changed::<ServerTick>),
                     Self::reset.run_if(server_just_stopped),
+                    Self::limit_bandwidth
+                        .after(ServerSet::Send)
+                        .before(ServerSet::SendPackets)
+                        .run_if(server_running),
                 ),
             );
 
@@ -162,6 +207,12 @@ impl Plugin for ServerPlugin {
 impl ServerPlugin {
     fn setup_channels(mut server: ResMut<RepliconServer>, channels: Res<RepliconChannels>) {
         server.setup_client_channels(channels.client_channels().len());
+        server.setup_server_channels(&channels);
+    }
+
+    /// Orders sent messages by channel priority and applies [`RepliconChannels::max_client_bps`].
+    fn limit_bandwidth(mut server: ResMut<RepliconServer>, time: Res<Time>) {
+        server.limit_bandwidth(time.delta());
     }
 
     /// Increments current server tick which causes the server to replicate this frame.
@@ -179,10 +230,16 @@ impl ServerPlugin {
         mut server: ResMut<RepliconServer>,
         mut client_buffers: ResMut<ClientBuffers>,
         mut buffered_events: ResMut<BufferedServerEvents>,
+        mut event_limiter: ResMut<ClientEventLimiter>,
+        stats: Option<ResMut<ServerReplicationStats>>,
     ) {
         match *trigger.event() {
             ServerEvent::ClientDisconnected { client_id, .. } => {
-                entity_map.0.remove(&client_id);
+                if let Some(mut stats) = stats {
+                    stats.clients.remove(&client_id);
+                }
+                entity_map.remove(client_id);
+                event_limiter.remove_client(client_id);
                 connected_clients.remove(client_id);
                 replicated_clients.remove(&mut client_buffers, client_id);
                 server.remove_client(client_id);
@@ -207,13 +264,39 @@ impl ServerPlugin {
 
     fn cleanup_acks(
         mutations_timeout: Duration,
-    ) -> impl FnMut(ResMut<ReplicatedClients>, ResMut<ClientBuffers>, Res<Time>) {
+    ) -> impl FnMut(
+        ResMut<ReplicatedClients>,
+        ResMut<ClientBuffers>,
+        Option<ResMut<ServerReplicationStats>>,
+        Res<Time>,
+    ) {
         move |mut replicated_clients: ResMut<ReplicatedClients>,
               mut client_buffers: ResMut<ClientBuffers>,
+              mut stats: Option<ResMut<ServerReplicationStats>>,
               time: Res<Time>| {
             let min_timestamp = time.elapsed().saturating_sub(mutations_timeout);
             for client in replicated_clients.iter_mut() {
-                client.cleanup_older_mutations(&mut client_buffers, min_timestamp);
+                let timed_out = client.cleanup_older_mutations(&mut client_buffers, min_timestamp);
+                if let Some(stats) = &mut stats {
+                    let client_stats = stats.client_mut(client.id());
+                    client_stats.timed_out_acks += timed_out;
+                    client_stats.pending_mutations = client.pending_mutations();
+                }
+            }
+        }
+    }
+
+    fn receive_resync_requests(
+        mut resync_events: EventReader<FromClient<ResyncRequest>>,
+        mut replicated_clients: ResMut<ReplicatedClients>,
+        mut client_buffers: ResMut<ClientBuffers>,
+    ) {
+        for &FromClient { client_id, .. } in resync_events.read() {
+            if let Some(client) = replicated_clients.get_client_mut(client_id) {
+                debug!("received resync request from `{client_id:?}`");
+                client.request_resync(&mut client_buffers);
+            } else {
+                debug!("ignoring resync request from `{client_id:?}` without started replication");
             }
         }
     }
@@ -246,6 +329,7 @@ impl ServerPlugin {
     /// Collects [`ReplicationMessages`] and sends them.
     pub(super) fn send_replication(
         mut serialized: Local<SerializedData>,
+        mut chunk_serialized: Local<Vec<SerializedData>>,
         mut messages: Local<ReplicationMessages>,
         mut replicated_archetypes: Local<ReplicatedArchetypes>,
         change_tick: SystemChangeTick,
@@ -255,10 +339,12 @@ impl ServerPlugin {
             ResMut<RemovalBuffer>,
             ResMut<ClientBuffers>,
             ResMut<ClientEntityMap>,
-            ResMut<DespawnBuffer>,
+            (ResMut<DespawnBuffer>, ResMut<ScopeBuffer>),
             ResMut<RepliconServer>,
+            Option<ResMut<ServerReplicationStats>>,
         )>,
         track_mutate_messages: Res<TrackMutateMessages>,
+        clients_per_task: Res<ClientsPerTask>,
         registry: Res<ReplicationRegistry>,
         rules: Res<ReplicationRules>,
         server_tick: Res<ServerTick>,
@@ -270,59 +356,89 @@ impl ServerPlugin {
         let mut replicated_clients = mem::take(&mut *set.p1());
         let mut removal_buffer = mem::take(&mut *set.p2());
         let mut client_buffers = mem::take(&mut *set.p3());
+        let mut stats = set.p7().map(|mut stats| mem::take(&mut *stats));
+        let (mut despawn_buffer, mut scope_buffer) = {
+            let (mut despawn_buffer, mut scope_buffer) = set.p5();
+            (
+                mem::take(&mut *despawn_buffer),
+                mem::take(&mut *scope_buffer),
+            )
+        };
 
         messages.reset(replicated_clients.len());
 
-        collect_mappings(
-            &mut messages,
-            &mut serialized,
-            &replicated_clients,
-            &mut set.p4(),
-        )?;
-        collect_despawns(
-            &mut messages,
-            &mut serialized,
-            &mut replicated_clients,
-            &mut set.p5(),
-        )?;
-        collect_removals(
-            &mut messages,
-            &mut serialized,
-            &replicated_clients,
-            &removal_buffer,
-        )?;
-        collect_changes(
-            &mut messages,
-            &mut serialized,
-            &mut replicated_clients,
-            &replicated_archetypes,
-            &registry,
-            &removal_buffer,
-            set.p0(),
-            &change_tick,
-            **server_tick,
-        )?;
-        removal_buffer.clear();
+        // Wrapped into a closure to return borrowed data back even on error.
+        let collect_and_send = || -> bincode::Result<()> {
+            let mut entity_map = mem::take(&mut *set.p4());
+            let result = collect_mappings(
+                &mut messages,
+                &mut serialized,
+                &replicated_clients,
+                &mut entity_map,
+                set.p0(),
+            );
+            *set.p4() = entity_map;
+            result?;
+            collect_despawns(
+                &mut messages,
+                &mut serialized,
+                &mut replicated_clients,
+                &mut despawn_buffer,
+                &mut scope_buffer,
+                set.p0(),
+            )?;
+            collect_removals(
+                &mut messages,
+                &mut serialized,
+                &replicated_clients,
+                &removal_buffer,
+                set.p0(),
+            )?;
+            collect_changes_parallel(
+                &mut messages,
+                &mut serialized,
+                &mut chunk_serialized,
+                &mut replicated_clients,
+                &replicated_archetypes,
+                &registry,
+                &removal_buffer,
+                set.p0(),
+                &change_tick,
+                **server_tick,
+                **clients_per_task,
+            )?;
 
-        send_messages(
-            &mut messages,
-            &mut replicated_clients,
-            &mut set.p6(),
-            **server_tick,
-            **track_mutate_messages,
-            &mut serialized,
-            &mut client_buffers,
-            change_tick,
-            &time,
-        )?;
+            send_messages(
+                &mut messages,
+                &mut replicated_clients,
+                &mut set.p6(),
+                **server_tick,
+                **track_mutate_messages,
+                &mut serialized,
+                &mut client_buffers,
+                change_tick,
+                &time,
+                stats.as_mut(),
+            )
+        };
+        let result = collect_and_send();
+        removal_buffer.clear();
         serialized.clear();
 
         // Return borrowed data back.
         *set.p1() = replicated_clients;
         *set.p2() = removal_buffer;
         *set.p3() = client_buffers;
+        {
+            let (mut despawn_buffer_res, mut scope_buffer_res) = set.p5();
+            *despawn_buffer_res = despawn_buffer;
+            *scope_buffer_res = scope_buffer;
+        }
+        if let Some(stats) = stats {
+            *set.p7().expect("stats shouldn't be removed during sending") = stats;
+        }
 
-        Ok(())
+        result
     }
 
     fn reset(
@@ -333,7 +449,7 @@ impl ServerPlugin {
         mut buffered_events: ResMut<BufferedServerEvents>,
     ) {
         *server_tick = Default::default();
-        entity_map.0.clear();
+        entity_map.clear();
         replicated_clients.clear(&mut client_buffers);
         buffered_events.clear();
     }
@@ -349,24 +465,37 @@ fn send_messages(
     client_buffers: &mut ClientBuffers,
     change_tick: SystemChangeTick,
     time: &Time,
+    mut stats: Option<&mut ServerReplicationStats>,
 ) -> Result<(), Box<bincode::ErrorKind>> {
     let mut server_tick_range = None;
     for ((update_message, mutate_message), client) in
         messages.iter_mut().zip(replicated_clients.iter_mut())
     {
+        let mut client_stats = stats.as_mut().map(|stats| stats.client_mut(client.id()));
+
+        if client.resync_pending() {
+            update_message.set_resync();
+            client.finish_resync();
+        }
+
         if !update_message.is_empty() {
             client.set_update_tick(server_tick);
-            let server_tick = write_tick_cached(&mut server_tick_range, serialized, server_tick)?;
+            let tick_range = write_tick_cached(&mut server_tick_range, serialized, server_tick)?;
 
             trace!("sending update message to {:?}", client.id());
-            update_message.send(server, client, serialized, server_tick)?;
+            update_message.send(
+                server,
+                client,
+                serialized,
+                server_tick,
+                tick_range,
+                client_stats.as_deref_mut(),
+            )?;
         } else {
             trace!("no updates to send for {:?}", client.id());
         }
 
         if !mutate_message.is_empty() || track_mutate_messages {
-            let server_tick = write_tick_cached(&mut server_tick_range, serialized, server_tick)?;
-
             let messages_count = mutate_message.send(
                 server,
                 client,
@@ -376,6 +505,7 @@ fn send_messages(
                 server_tick,
                 change_tick.this_run(),
                 time.elapsed(),
+                client_stats.as_deref_mut(),
             )?;
             trace!(
                 "sending {messages_count} mutate message(s) to {:?}",
@@ -385,6 +515,10 @@ fn send_messages(
             trace!("no mutations to send for {:?}", client.id());
         }
 
+        if let Some(client_stats) = client_stats {
+            client_stats.pending_mutations = client.pending_mutations();
+        }
+
         client.visibility_mut().update();
     }
 
@@ -392,17 +526,59 @@ fn send_messages(
 }
 
 /// Collects and writes any new entity mappings that happened in this tick.
+///
+/// Mappings that can't be applied on the client, including mappings for hidden or out-of-scope entities,
+/// are written together with rejected entities as unmapped client entities.
 fn collect_mappings(
     messages: &mut ReplicationMessages,
     serialized: &mut SerializedData,
     replicated_clients: &ReplicatedClients,
     entity_map: &mut ClientEntityMap,
+    world: &World,
 ) -> bincode::Result<()> {
+    let mut dropped = Vec::new();
     for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter()) {
-        if let Some(mappings) = entity_map.0.get_mut(&client.id()) {
-            let len = mappings.len();
-            let mappings = serialized.write_mappings(mappings.drain(..))?;
-            message.set_mappings(mappings, len);
+        let (mappings, rejected) = entity_map.get_mut(client.id());
+        if let Some(mappings) = mappings {
+            let start = serialized.len();
+            let mut len = 0;
+            for mapping in mappings.drain(..) {
+                let replicated = world.get_entity(mapping.server_entity).is_ok_and(|entity| {
+                    entity.contains::<Replicated>()
+                        && entity
+                            .get::<ReplicationScope>()
+                            .is_none_or(|&scope| client.in_scope(scope))
+                });
+                if replicated
+                    && client.visibility().is_visible(mapping.server_entity)
+                    && client.mutation_tick(mapping.server_entity).is_none()
+                {
+                    serialized.write_mapping(mapping)?;
+                    len += 1;
+                } else {
+                    debug!(
+                        "dropping mapping from {:?} to {:?} for `{:?}` because the server entity can't be replicated to it",
+                        mapping.server_entity,
+                        mapping.client_entity,
+                        client.id()
+                    );
+                    dropped.push(mapping.client_entity);
+                }
+            }
+            if len != 0 {
+                message.set_mappings(start..serialized.len(), len);
+            }
+        }
+
+        let rejected = rejected.map(|rejected| rejected.drain(..));
+        let unmapped = rejected
+            .into_iter()
+            .flatten()
+            .map(|entity| (entity, false))
+            .chain(dropped.drain(..).map(|entity| (entity, true)));
+        let (unmapped, len) = serialized.write_unmapped(unmapped)?;
+        if len != 0 {
+            message.set_unmapped(unmapped, len);
         }
     }
 
@@ -410,29 +586,53 @@ fn collect_mappings(
 }
 
 /// Collect entity despawns from this tick into update messages.
+///
+/// Entities that are no longer in the client's scopes are also despawned.
 fn collect_despawns(
     messages: &mut ReplicationMessages,
     serialized: &mut SerializedData,
     replicated_clients: &mut ReplicatedClients,
     despawn_buffer: &mut DespawnBuffer,
+    scope_buffer: &mut ScopeBuffer,
+    world: &World,
 ) -> bincode::Result<()> {
-    for entity in despawn_buffer.drain(..) {
+    for &entity in despawn_buffer.iter() {
+        let scoped = despawn_buffer.is_scoped(entity);
         let entity_range = serialized.write_entity(entity)?;
         for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter_mut()) {
-            if client.visibility().is_visible(entity) {
+            // Scoped entities could be skipped for the client, so check if it received them.
+            if client.visibility().is_visible(entity)
+                && (!scoped || client.mutation_tick(entity).is_some())
+            {
                 message.add_despawn(entity_range.clone());
             }
             client.remove_despawned(entity);
         }
     }
+    despawn_buffer.clear();
 
     for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter_mut()) {
+        let resync = client.resync_pending();
         for entity in client.drain_lost_visibility() {
+            // Resync replaces the client state, so entities that aren't included will be despawned anyway.
+            if resync {
+                continue;
+            }
             let entity_range = serialized.write_entity(entity)?;
             message.add_despawn(entity_range);
         }
     }
 
+    let mut lost = Vec::new();
+    for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter_mut()) {
+        client.remove_out_of_scope(world, scope_buffer, &mut lost);
+        for entity in lost.drain(..) {
+            let entity_range = serialized.write_entity(entity)?;
+            message.add_despawn(entity_range);
+        }
+    }
+    scope_buffer.clear();
+
     Ok(())
 }
 
@@ -442,12 +642,17 @@ fn collect_removals(
     serialized: &mut SerializedData,
     replicated_clients: &ReplicatedClients,
     removal_buffer: &RemovalBuffer,
+    world: &World,
 ) -> bincode::Result<()> {
     for (&entity, remove_ids) in removal_buffer.iter() {
+        let scope = world.get::<ReplicationScope>(entity);
         let entity_range = serialized.write_entity(entity)?;
         let ids_len = remove_ids.len();
         let fn_ids = serialized.write_fn_ids(remove_ids.iter().map(|&(_, fns_id)| fns_id))?;
         for ((message, _), client) in messages.iter_mut().zip(replicated_clients.iter()) {
+            if scope.is_some_and(|&scope| !client.in_scope(scope)) {
+                continue;
+            }
             if client.visibility().is_visible(entity) {
                 message.add_removals(entity_range.clone(), ids_len, fn_ids.clone());
             }
@@ -457,10 +662,14 @@ fn collect_removals(
     Ok(())
 }
 
-/// Collects component changes from this tick into update and mutate messages since the last entity tick.
-fn collect_changes(
+/// Splits clients into chunks and runs [`collect_changes`] for each chunk on [`ComputeTaskPool`].
+///
+/// Each chunk except the first one serializes data into its own buffer from `chunk_serialized`.
+/// After collection, these buffers are appended to `serialized` and message ranges are shifted accordingly.
+fn collect_changes_parallel(
     messages: &mut ReplicationMessages,
     serialized: &mut SerializedData,
+    chunk_serialized: &mut Vec<SerializedData>,
     replicated_clients: &mut ReplicatedClients,
     replicated_archetypes: &ReplicatedArchetypes,
     registry: &ReplicationRegistry,
@@ -468,8 +677,94 @@ fn collect_changes(
     world: &World,
     change_tick: &SystemChangeTick,
     server_tick: RepliconTick,
+    clients_per_task: usize,
 ) -> bincode::Result<()> {
+    let chunks_count = replicated_clients.len().div_ceil(clients_per_task);
+    if chunks_count <= 1 {
+        let Some((messages, clients)) = messages
+            .chunks_mut(clients_per_task)
+            .zip(replicated_clients.chunks_mut(clients_per_task))
+            .next()
+        else {
+            return Ok(());
+        };
+
+        return collect_changes(
+            messages,
+            serialized,
+            clients,
+            replicated_archetypes,
+            registry,
+            removal_buffer,
+            world,
+            change_tick,
+            server_tick,
+        );
+    }
+
+    chunk_serialized.resize_with(chunks_count - 1, Default::default);
+    let buffers = [&mut *serialized]
+        .into_iter()
+        .chain(chunk_serialized.iter_mut());
+    let task_pool = ComputeTaskPool::get();
+    let results = task_pool.scope(|scope| {
+        for ((messages, clients), serialized) in messages
+            .chunks_mut(clients_per_task)
+            .zip(replicated_clients.chunks_mut(clients_per_task))
+            .zip(buffers)
+        {
+            scope.spawn(async move {
+                collect_changes(
+                    messages,
+                    serialized,
+                    clients,
+                    replicated_archetypes,
+                    registry,
+                    removal_buffer,
+                    world,
+                    change_tick,
+                    server_tick,
+                )
+            });
+        }
+    });
+    results.into_iter().collect::<bincode::Result<()>>()?;
+
+    for (messages, chunk) in messages
+        .chunks_mut(clients_per_task)
+        .skip(1)
+        .zip(chunk_serialized.iter_mut())
+    {
+        let offset = serialized.len();
+        serialized.append(chunk);
+        for (update_message, mutate_message) in messages {
+            update_message.shift_changes(offset);
+            mutate_message.shift_mutations(offset);
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects component changes from this tick into update and mutate messages since the last entity tick.
+fn collect_changes(
+    messages: &mut [(UpdateMessage, MutateMessage)],
+    serialized: &mut SerializedData,
+    replicated_clients: &mut [ReplicatedClient],
+    replicated_archetypes: &ReplicatedArchetypes,
+    registry: &ReplicationRegistry,
+    removal_buffer: &RemovalBuffer,
+    world: &World,
+    change_tick: &SystemChangeTick,
+    server_tick: RepliconTick,
+) -> bincode::Result<()> {
+    let scope_clients = ScopeClients::new(replicated_clients);
     for replicated_archetype in replicated_archetypes.iter() {
+        if replicated_archetype.scope_storage.is_some() && scope_clients.is_empty() {
+            // None of the clients can see entities from any scope.
+            continue;
+        }
+
         // SAFETY: all IDs from replicated archetypes obtained from real archetypes.
         let archetype = unsafe {
             world
@@ -487,11 +782,41 @@ fn collect_changes(
         };
 
         for entity in archetype.entities() {
+            let scope = replicated_archetype.scope_storage.map(|storage_type| {
+                // SAFETY: component and storage were obtained from this archetype.
+                let (scope, _) = unsafe {
+                    get_component_unchecked(
+                        table,
+                        &world.storages().sparse_sets,
+                        entity,
+                        storage_type,
+                        replicated_archetypes.scope_id(),
+                    )
+                };
+                // SAFETY: pointer obtained for the scope component ID.
+                unsafe { *scope.deref::<ReplicationScope>() }
+            });
+            let client_indices = scope_clients.get(scope);
+            if client_indices.is_empty() {
+                continue;
+            }
+
             let mut entity_range = None;
-            for ((update_message, mutate_message), client) in
-                messages.iter_mut().zip(replicated_clients.iter())
-            {
-                let visibility = client.visibility().state(entity.id());
+            for &index in client_indices {
+                let (update_message, mutate_message) = &mut messages[index];
+                let client = &replicated_clients[index];
+                let mut visibility = client.visibility().state(entity.id());
+                if scope.is_some()
+                    && visibility == Visibility::Visible
+                    && client.mutation_tick(entity.id()).is_none()
+                {
+                    // Just entered the client's scopes.
+                    visibility = Visibility::Gained;
+                }
+                if client.resync_pending() && visibility == Visibility::Visible {
+                    // Resend as new to replace the client state.
+                    visibility = Visibility::Gained;
+                }
                 update_message.start_entity_changes(visibility);
                 mutate_message.start_entity_mutations();
             }
@@ -532,9 +857,9 @@ fn collect_changes(
                     component_id,
                 };
                 let mut component_range = None;
-                for ((update_message, mutate_message), client) in
-                    messages.iter_mut().zip(replicated_clients.iter())
-                {
+                for &index in client_indices {
+                    let (update_message, mutate_message) = &mut messages[index];
+                    let client = &replicated_clients[index];
                     if update_message.entity_visibility() == Visibility::Hidden {
                         continue;
                     }
@@ -585,9 +910,9 @@ fn collect_changes(
                 }
             }
 
-            for ((update_message, mutate_message), client) in
-                messages.iter_mut().zip(replicated_clients.iter_mut())
-            {
+            for &index in client_indices {
+                let (update_message, mutate_message) = &mut messages[index];
+                let client = &mut replicated_clients[index];
                 let visibility = update_message.entity_visibility();
                 if visibility == Visibility::Hidden {
                     continue;
@@ -709,6 +1034,91 @@ fn write_tick_cached(
     Ok(range)
 }
 
+/// Replication stats for each client during server operation.
+///
+/// Statistics are collected only if the resource is present.
+/// The resource is not added by default.
+///
+/// Stats for a client are removed after its disconnection.
+///
+/// See also [`ServerDiagnosticsPlugin`](diagnostics::ServerDiagnosticsPlugin)
+/// for automatic integration with Bevy diagnostics.
+#[derive(Default, Resource, Debug)]
+pub struct ServerReplicationStats {
+    clients: HashMap<ClientId, ReplicatedClientStats>,
+}
+
+impl ServerReplicationStats {
+    /// Returns stats for a client.
+    pub fn get(&self, client_id: ClientId) -> Option<&ReplicatedClientStats> {
+        self.clients.get(&client_id)
+    }
+
+    /// Returns an iterator over stats for all clients.
+    pub fn iter(&self) -> impl Iterator<Item = (ClientId, &ReplicatedClientStats)> {
+        self.clients
+            .iter()
+            .map(|(&client_id, stats)| (client_id, stats))
+    }
+
+    /// Returns stats summed for all clients.
+    pub fn total(&self) -> ReplicatedClientStats {
+        self.clients
+            .values()
+            .fold(Default::default(), |total, stats| total + *stats)
+    }
+
+    fn client_mut(&mut self, client_id: ClientId) -> &mut ReplicatedClientStats {
+        self.clients.entry(client_id).or_default()
+    }
+}
+
+/// Replication stats for a single client.
+///
+/// See also [`ServerReplicationStats`].
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct ReplicatedClientStats {
+    /// Incremented per entity included into an update or mutate message.
+    pub entities: usize,
+    /// Incremented for every component included into an update or mutate message.
+    pub components: usize,
+    /// Update messages sent.
+    pub update_messages: usize,
+    /// Bytes sent in update message payloads.
+    pub update_bytes: usize,
+    /// Mutate messages sent, incremented for each part after splitting.
+    pub mutate_messages: usize,
+    /// Bytes sent in mutate message payloads.
+    pub mutate_bytes: usize,
+    /// Number of mutate messages that weren't acknowledged yet.
+    ///
+    /// Updated on each sending and cleanup of old mutate messages.
+    pub pending_mutations: usize,
+    /// Incremented per mutate message that wasn't acknowledged within [`ServerPlugin::mutations_timeout`].
+    pub timed_out_acks: usize,
+}
+
+impl Add for ReplicatedClientStats {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            entities: self.entities + rhs.entities,
+            components: self.components + rhs.components,
+            update_messages: self.update_messages + rhs.update_messages,
+            update_bytes: self.update_bytes + rhs.update_bytes,
+            mutate_messages: self.mutate_messages + rhs.mutate_messages,
+            mutate_bytes: self.mutate_bytes + rhs.mutate_bytes,
+            pending_mutations: self.pending_mutations + rhs.pending_mutations,
+            timed_out_acks: self.timed_out_acks + rhs.timed_out_acks,
+        }
+    }
+}
+
+/// Value of [`ServerPlugin::clients_per_task`].
+#[derive(Resource, Deref)]
+pub(crate) struct ClientsPerTask(usize);
+
 /// Set with replication and event systems related to server.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum ServerSet {
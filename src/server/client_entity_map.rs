@@ -33,8 +33,10 @@ struct SpawnBullet(Entity);
 struct Bullet;
 
 /// System that shoots a bullet and spawns it on the client.
+///
+/// `PendingMapping` is optional and used to clean up the bullet if the server never confirms it.
 fn shoot_bullet(mut commands: Commands, mut bullet_events: EventWriter<SpawnBullet>) {
-    let entity = commands.spawn(Bullet).id();
+    let entity = commands.spawn((Bullet, PendingMapping::default())).id();
     bullet_events.send(SpawnBullet(entity));
 }
 
@@ -68,9 +70,21 @@ client entity.
 
 If client's original entity is not found, a new entity will be spawned on the client,
 just the same as when no client entity is provided.
+
+If the server entity is not visible to the client, isn't replicated, or was already replicated
+to the client, the mapping can't be applied and will be reported to the client as dropped.
+Use [`Self::reject`] to explicitly notify the client that its pre-spawned entity won't be confirmed.
+See [`MappingOutcome`](crate::client::pending_mapping::MappingOutcome) for details about client-side handling.
 **/
 #[derive(Resource, Debug, Default, Deref)]
-pub struct ClientEntityMap(pub(super) HashMap<ClientId, Vec<ClientMapping>>);
+pub struct ClientEntityMap {
+    /// Mappings that will be sent to each client.
+    #[deref]
+    mappings: HashMap<ClientId, Vec<ClientMapping>>,
+
+    /// Client entities that were rejected by the server for each client.
+    rejected: HashMap<ClientId, Vec<Entity>>,
+}
 
 impl ClientEntityMap {
     /// Registers `mapping` for a client entity pre-spawned by the specified client.
@@ -78,7 +92,40 @@ impl ClientEntityMap {
     /// This will be sent as part of replication data and added to the client's
     /// [`ServerEntityMap`](crate::core::server_entity_map::ServerEntityMap).
     pub fn insert(&mut self, client_id: ClientId, mapping: ClientMapping) {
-        self.0.entry(client_id).or_default().push(mapping);
+        self.mappings.entry(client_id).or_default().push(mapping);
+    }
+
+    /// Rejects a client entity pre-spawned by the specified client.
+    ///
+    /// The client will be notified that the entity won't be mapped to any server entity.
+    pub fn reject(&mut self, client_id: ClientId, client_entity: Entity) {
+        self.rejected
+            .entry(client_id)
+            .or_default()
+            .push(client_entity);
+    }
+
+    /// Returns pending mappings and rejected entities for a client.
+    pub(super) fn get_mut(
+        &mut self,
+        client_id: ClientId,
+    ) -> (Option<&mut Vec<ClientMapping>>, Option<&mut Vec<Entity>>) {
+        (
+            self.mappings.get_mut(&client_id),
+            self.rejected.get_mut(&client_id),
+        )
+    }
+
+    /// Removes all pending data for a client.
+    pub(super) fn remove(&mut self, client_id: ClientId) {
+        self.mappings.remove(&client_id);
+        self.rejected.remove(&client_id);
+    }
+
+    /// Removes all pending data.
+    pub(super) fn clear(&mut self) {
+        self.mappings.clear();
+        self.rejected.clear();
     }
 }
 
@@ -1,7 +1,11 @@
-use bevy::prelude::*;
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
 
 use super::{ServerPlugin, ServerSet};
-use crate::core::{common_conditions::server_running, replication::Replicated};
+use crate::core::{
+    common_conditions::server_running,
+    replication::{replication_scope::ReplicationScope, Replicated},
+    replicon_server::RepliconServer,
+};
 
 /// Treats removals of [`Replicated`] component as despawns and stores them into [`DespawnBuffer`] resource.
 ///
@@ -10,17 +14,33 @@ pub(super) struct DespawnBufferPlugin;
 
 impl Plugin for DespawnBufferPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<DespawnBuffer>().add_systems(
-            PostUpdate,
-            Self::buffer_despawns
-                .before(ServerPlugin::send_replication)
-                .in_set(ServerSet::Send)
-                .run_if(server_running),
-        );
+        app.init_resource::<DespawnBuffer>()
+            .add_observer(Self::buffer_scoped)
+            .add_systems(
+                PostUpdate,
+                Self::buffer_despawns
+                    .before(ServerPlugin::send_replication)
+                    .in_set(ServerSet::Send)
+                    .run_if(server_running),
+            );
     }
 }
 
 impl DespawnBufferPlugin {
+    /// Remembers entities that had [`ReplicationScope`] at the moment of despawn.
+    ///
+    /// The component is no longer available when the despawn is read from [`RemovedComponents`].
+    fn buffer_scoped(
+        trigger: Trigger<OnRemove, Replicated>,
+        scoped: Query<(), With<ReplicationScope>>,
+        server: Res<RepliconServer>,
+        mut despawn_buffer: ResMut<DespawnBuffer>,
+    ) {
+        if server.is_running() && scoped.get(trigger.entity()).is_ok() {
+            despawn_buffer.scoped.insert(trigger.entity());
+        }
+    }
+
     fn buffer_despawns(
         mut removed_replications: RemovedComponents<Replicated>,
         mut despawn_buffer: ResMut<DespawnBuffer>,
@@ -35,7 +55,28 @@ impl DespawnBufferPlugin {
 ///
 /// Should be cleaned up manually.
 #[derive(Default, Resource, Deref, DerefMut)]
-pub(crate) struct DespawnBuffer(Vec<Entity>);
+pub(crate) struct DespawnBuffer {
+    #[deref]
+    entities: Vec<Entity>,
+
+    /// Despawned entities that had [`ReplicationScope`].
+    scoped: EntityHashSet,
+}
+
+impl DespawnBuffer {
+    /// Returns `true` if the entity had [`ReplicationScope`] when it was despawned.
+    pub(crate) fn is_scoped(&self, entity: Entity) -> bool {
+        self.scoped.contains(&entity)
+    }
+
+    /// Clears all entities.
+    ///
+    /// Keeps the allocated memory for reuse.
+    pub(crate) fn clear(&mut self) {
+        self.entities.clear();
+        self.scoped.clear();
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -61,4 +102,32 @@ mod tests {
         let despawn_buffer = app.world().resource::<DespawnBuffer>();
         assert_eq!(despawn_buffer.len(), 1);
     }
+
+    #[test]
+    fn scoped_despawns() {
+        let mut app = App::new();
+        app.add_plugins(DespawnBufferPlugin)
+            .init_resource::<RepliconServer>();
+
+        app.world_mut()
+            .resource_mut::<RepliconServer>()
+            .set_running(true);
+
+        app.update();
+
+        let entity = app.world_mut().spawn(Replicated).id();
+        let scoped_entity = app
+            .world_mut()
+            .spawn((Replicated, ReplicationScope(1)))
+            .id();
+        app.world_mut().despawn(entity);
+        app.world_mut().despawn(scoped_entity);
+
+        app.update();
+
+        let despawn_buffer = app.world().resource::<DespawnBuffer>();
+        assert_eq!(despawn_buffer.len(), 2);
+        assert!(!despawn_buffer.is_scoped(entity));
+        assert!(despawn_buffer.is_scoped(scoped_entity));
+    }
 }
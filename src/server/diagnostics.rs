@@ -0,0 +1,139 @@
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    utils::HashMap,
+};
+
+use super::{ReplicatedClientStats, ServerReplicationStats, ServerSet};
+use crate::core::{common_conditions::server_running, ClientId};
+
+/// Plugin to write [`Diagnostics`] based on [`ServerReplicationStats`] every tick.
+///
+/// Measurements are summed for all clients, use [`ServerReplicationStats`] for per-client values.
+///
+/// Adds [`ServerReplicationStats`] resource.
+pub struct ServerDiagnosticsPlugin;
+
+impl Plugin for ServerDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServerReplicationStats>()
+            .add_systems(
+                PostUpdate,
+                Self::add_measurements
+                    .after(ServerSet::Send)
+                    .run_if(server_running),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::ENTITIES)
+                    .with_suffix(" entities")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::COMPONENTS)
+                    .with_suffix(" components")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::UPDATE_MESSAGES)
+                    .with_suffix(" update messages")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::UPDATE_BYTES)
+                    .with_suffix(" update bytes")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::MUTATE_MESSAGES)
+                    .with_suffix(" mutate messages")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::MUTATE_BYTES)
+                    .with_suffix(" mutate bytes")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::PENDING_MUTATIONS)
+                    .with_suffix(" pending mutate messages")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::TIMED_OUT_ACKS)
+                    .with_suffix(" timed out acks")
+                    .with_max_history_length(Self::DIAGNOSTIC_HISTORY_LEN),
+            );
+    }
+}
+
+impl ServerDiagnosticsPlugin {
+    /// How many entities sent.
+    pub const ENTITIES: DiagnosticPath = DiagnosticPath::const_new("server/replication/entities");
+    /// How many components sent.
+    pub const COMPONENTS: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/components");
+    /// How many update messages sent.
+    pub const UPDATE_MESSAGES: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/update_messages");
+    /// How many bytes sent in update messages.
+    pub const UPDATE_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/update_bytes");
+    /// How many mutate messages sent.
+    pub const MUTATE_MESSAGES: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/mutate_messages");
+    /// How many bytes sent in mutate messages.
+    pub const MUTATE_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/mutate_bytes");
+    /// How many mutate messages are waiting for acknowledgment.
+    pub const PENDING_MUTATIONS: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/pending_mutations");
+    /// How many mutate messages weren't acknowledged in time.
+    pub const TIMED_OUT_ACKS: DiagnosticPath =
+        DiagnosticPath::const_new("server/replication/timed_out_acks");
+
+    /// Max diagnostic history length.
+    pub const DIAGNOSTIC_HISTORY_LEN: usize = 60;
+
+    fn add_measurements(
+        mut diagnostics: Diagnostics,
+        stats: Res<ServerReplicationStats>,
+        mut last_stats: Local<HashMap<ClientId, ReplicatedClientStats>>,
+    ) {
+        // Sum only differences for each client to ignore stats of disconnected clients.
+        // Saturate in case the client reconnected with the same ID between measurements.
+        let mut delta = ReplicatedClientStats::default();
+        for (client_id, client_stats) in stats.iter() {
+            let last = last_stats.get(&client_id).copied().unwrap_or_default();
+            delta.entities += client_stats.entities.saturating_sub(last.entities);
+            delta.components += client_stats.components.saturating_sub(last.components);
+            delta.update_messages += client_stats
+                .update_messages
+                .saturating_sub(last.update_messages);
+            delta.update_bytes += client_stats.update_bytes.saturating_sub(last.update_bytes);
+            delta.mutate_messages += client_stats
+                .mutate_messages
+                .saturating_sub(last.mutate_messages);
+            delta.mutate_bytes += client_stats.mutate_bytes.saturating_sub(last.mutate_bytes);
+            delta.pending_mutations += client_stats.pending_mutations;
+            delta.timed_out_acks += client_stats
+                .timed_out_acks
+                .saturating_sub(last.timed_out_acks);
+        }
+
+        diagnostics.add_measurement(&Self::ENTITIES, || delta.entities as f64);
+        diagnostics.add_measurement(&Self::COMPONENTS, || delta.components as f64);
+        diagnostics.add_measurement(&Self::UPDATE_MESSAGES, || delta.update_messages as f64);
+        diagnostics.add_measurement(&Self::UPDATE_BYTES, || delta.update_bytes as f64);
+        diagnostics.add_measurement(&Self::MUTATE_MESSAGES, || delta.mutate_messages as f64);
+        diagnostics.add_measurement(&Self::MUTATE_BYTES, || delta.mutate_bytes as f64);
+        diagnostics.add_measurement(&Self::PENDING_MUTATIONS, || delta.pending_mutations as f64);
+        diagnostics.add_measurement(&Self::TIMED_OUT_ACKS, || delta.timed_out_acks as f64);
+
+        last_stats.clear();
+        last_stats.extend(
+            stats
+                .iter()
+                .map(|(client_id, client_stats)| (client_id, *client_stats)),
+        );
+    }
+}
@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use super::{server_tick::ServerTick, ServerPlugin, ServerSet};
+use super::{
+    event_limits::{ClientEventLimitExceeded, ClientEventLimiter},
+    server_tick::ServerTick,
+    ServerPlugin, ServerSet,
+};
 use crate::core::{
     common_conditions::*,
     connected_clients::ConnectedClients,
@@ -95,6 +99,21 @@ impl ServerEventPlugin {
                         registry: &registry.read(),
                     };
 
+                    let now = world.resource::<Time<Real>>().elapsed();
+                    world.resource_scope(|world, mut limiter: Mut<ClientEventLimiter>| {
+                        let mut exceeded = world.resource_mut::<Events<ClientEventLimitExceeded>>();
+                        for event_data in event_registry.iter_client_events() {
+                            limiter.apply(
+                                &mut server,
+                                event_data.channel_id(),
+                                event_data.limits_id(),
+                                event_data.limits_name(),
+                                now,
+                                &mut exceeded,
+                            );
+                        }
+                    });
+
                     for event_data in event_registry.iter_client_events() {
                         let client_events = world
                             .get_resource_mut_by_id(event_data.client_events_id())
@@ -0,0 +1,213 @@
+use std::{any::TypeId, time::Duration};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::core::{replicon_server::RepliconServer, ClientId};
+
+/// Limits for receiving a client event on server.
+///
+/// All limits are applied per client. Events that exceed any limit are discarded before deserialization
+/// and reported via [`ClientEventLimitExceeded`].
+///
+/// By default no limits are set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClientEventLimits {
+    /// Maximum number of events per second.
+    ///
+    /// Events are counted in fixed one-second windows that start with the first event after the previous window ends.
+    /// So up to twice this number of events can be received within a second across a window boundary.
+    pub max_per_second: Option<u32>,
+
+    /// Maximum size of a single serialized event in bytes.
+    pub max_size: Option<usize>,
+
+    /// Maximum number of events that will be received in a single update.
+    ///
+    /// Useful to limit bursts that arrive within a single frame.
+    pub max_per_update: Option<usize>,
+}
+
+/// Configured limits for client events and their current usage.
+///
+/// Limits can be set for an event type and overridden for specific clients.
+/// Triggers and requests are limited by their trigger or request type.
+///
+/// Inserted as resource by [`ServerPlugin`](super::ServerPlugin).
+#[derive(Resource, Default)]
+pub struct ClientEventLimiter {
+    /// Limits for each event type.
+    event_limits: HashMap<TypeId, ClientEventLimits>,
+
+    /// Per-client overrides for event types.
+    client_limits: HashMap<(ClientId, TypeId), ClientEventLimits>,
+
+    /// Rate windows for each client and event type.
+    windows: HashMap<(ClientId, TypeId), RateWindow>,
+}
+
+impl ClientEventLimiter {
+    /// Sets limits for the client event `E` for all clients.
+    pub fn set_limits<E: Event>(&mut self, limits: ClientEventLimits) {
+        self.event_limits.insert(TypeId::of::<E>(), limits);
+    }
+
+    /// Sets limits for the client event `E` for a specific client.
+    ///
+    /// Overrides limits from [`Self::set_limits`]. Removed when the client disconnects.
+    pub fn set_client_limits<E: Event>(&mut self, client_id: ClientId, limits: ClientEventLimits) {
+        self.client_limits
+            .insert((client_id, TypeId::of::<E>()), limits);
+    }
+
+    /// Removes limits for the client event `E` set by [`Self::set_client_limits`].
+    pub fn remove_client_limits<E: Event>(&mut self, client_id: ClientId) {
+        self.client_limits.remove(&(client_id, TypeId::of::<E>()));
+    }
+
+    /// Returns limits for the client event `E` that apply to a specific client.
+    pub fn limits<E: Event>(&self, client_id: ClientId) -> ClientEventLimits {
+        self.limits_by_id(client_id, TypeId::of::<E>())
+    }
+
+    fn limits_by_id(&self, client_id: ClientId, event_id: TypeId) -> ClientEventLimits {
+        find_limits(&self.event_limits, &self.client_limits, client_id, event_id)
+    }
+
+    /// Discards received messages for an event that exceed the limits.
+    ///
+    /// Should be called before the messages are deserialized.
+    pub(crate) fn apply(
+        &mut self,
+        server: &mut RepliconServer,
+        channel_id: u8,
+        event_id: TypeId,
+        event_name: &'static str,
+        now: Duration,
+        exceeded: &mut Events<ClientEventLimitExceeded>,
+    ) {
+        if self.event_limits.is_empty() && self.client_limits.is_empty() {
+            return;
+        }
+
+        let mut received = HashMap::<ClientId, usize>::default();
+        server.retain_received(channel_id, |client_id, message| {
+            let limits = find_limits(&self.event_limits, &self.client_limits, client_id, event_id);
+            let violation =
+                if let Some(max_size) = limits.max_size.filter(|&max| message.len() > max) {
+                    Some(LimitViolation::Size {
+                        size: message.len(),
+                        max_size,
+                    })
+                } else if let Some(max_per_update) = limits.max_per_update.filter(|&max| {
+                    let count = received.entry(client_id).or_default();
+                    *count += 1;
+                    *count > max
+                }) {
+                    Some(LimitViolation::PerUpdate { max_per_update })
+                } else if let Some(max_per_second) = limits.max_per_second {
+                    let window = self.windows.entry((client_id, event_id)).or_default();
+                    (!window.try_add(now, max_per_second))
+                        .then_some(LimitViolation::Rate { max_per_second })
+                } else {
+                    None
+                };
+
+            if let Some(violation) = violation {
+                debug!("discarding event `{event_name}` from `{client_id:?}`: {violation:?}");
+                exceeded.send(ClientEventLimitExceeded {
+                    client_id,
+                    event_name,
+                    violation,
+                });
+                return false;
+            }
+
+            true
+        });
+    }
+
+    /// Removes all per-client limits and usage.
+    pub(crate) fn remove_client(&mut self, client_id: ClientId) {
+        self.client_limits
+            .retain(|&(limit_client_id, _), _| limit_client_id != client_id);
+        self.windows
+            .retain(|&(window_client_id, _), _| window_client_id != client_id);
+    }
+}
+
+fn find_limits(
+    event_limits: &HashMap<TypeId, ClientEventLimits>,
+    client_limits: &HashMap<(ClientId, TypeId), ClientEventLimits>,
+    client_id: ClientId,
+    event_id: TypeId,
+) -> ClientEventLimits {
+    client_limits
+        .get(&(client_id, event_id))
+        .or_else(|| event_limits.get(&event_id))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Number of events received within a fixed one-second window.
+#[derive(Default)]
+struct RateWindow {
+    start: Duration,
+    count: u32,
+}
+
+impl RateWindow {
+    /// Counts an event and returns `false` if it exceeds the limit for the current window.
+    fn try_add(&mut self, now: Duration, max_per_second: u32) -> bool {
+        if now.saturating_sub(self.start) >= Duration::from_secs(1) {
+            self.start = now;
+            self.count = 0;
+        }
+
+        if self.count >= max_per_second {
+            return false;
+        }
+
+        self.count += 1;
+        true
+    }
+}
+
+/// An event that indicates that a client event was discarded because it exceeded [`ClientEventLimits`].
+///
+/// Emitted only on server. Could be used to log or disconnect misbehaving clients.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientEventLimitExceeded {
+    /// Client that sent the event.
+    pub client_id: ClientId,
+
+    /// Type name of the discarded event.
+    pub event_name: &'static str,
+
+    /// The exceeded limit.
+    pub violation: LimitViolation,
+}
+
+/// Limit from [`ClientEventLimits`] that was exceeded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitViolation {
+    /// More than [`ClientEventLimits::max_per_second`] events were received within a second.
+    Rate { max_per_second: u32 },
+    /// The serialized event is larger than [`ClientEventLimits::max_size`].
+    Size { size: usize, max_size: usize },
+    /// More than [`ClientEventLimits::max_per_update`] events were received in a single update.
+    PerUpdate { max_per_update: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_window() {
+        let mut window = RateWindow::default();
+        assert!(window.try_add(Duration::ZERO, 2));
+        assert!(window.try_add(Duration::from_millis(500), 2));
+        assert!(!window.try_add(Duration::from_millis(999), 2));
+        assert!(window.try_add(Duration::from_secs(1), 2));
+    }
+}
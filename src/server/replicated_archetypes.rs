@@ -11,7 +11,8 @@ use bevy::{
 };
 
 use crate::core::replication::{
-    replication_registry::FnsId, replication_rules::ReplicationRules, Replicated,
+    replication_registry::FnsId, replication_rules::ReplicationRules,
+    replication_scope::ReplicationScope, Replicated,
 };
 
 /// Cached information about all replicated archetypes.
@@ -20,6 +21,9 @@ pub(crate) struct ReplicatedArchetypes {
     /// ID of [`Replicated`] component.
     marker_id: ComponentId,
 
+    /// ID of [`ReplicationScope`] component.
+    scope_id: ComponentId,
+
     /// Highest processed archetype ID.
     generation: ArchetypeGeneration,
 
@@ -34,6 +38,11 @@ impl ReplicatedArchetypes {
         self.marker_id
     }
 
+    /// ID of the [`ReplicationScope`] component.
+    pub(crate) fn scope_id(&self) -> ComponentId {
+        self.scope_id
+    }
+
     /// Updates the internal view of the [`World`]'s replicated archetypes.
     ///
     /// If this is not called before querying data, the results may not accurately reflect what is in the world.
@@ -46,6 +55,7 @@ impl ReplicatedArchetypes {
             .filter(|archetype| archetype.contains(self.marker_id))
         {
             let mut replicated_archetype = ReplicatedArchetype::new(archetype.id());
+            replicated_archetype.scope_storage = archetype.get_storage_type(self.scope_id);
             for rule in rules.iter().filter(|rule| rule.matches(archetype)) {
                 for &(component_id, fns_id) in &rule.components {
                     // Since rules are sorted by priority,
@@ -95,6 +105,7 @@ impl FromWorld for ReplicatedArchetypes {
     fn from_world(world: &mut World) -> Self {
         Self {
             marker_id: world.register_component::<Replicated>(),
+            scope_id: world.register_component::<ReplicationScope>(),
             generation: ArchetypeGeneration::initial(),
             archetypes: Default::default(),
         }
@@ -108,6 +119,9 @@ pub(crate) struct ReplicatedArchetype {
 
     /// Components marked as replicated.
     pub(super) components: Vec<ReplicatedComponent>,
+
+    /// Storage type of [`ReplicationScope`] if the archetype contains it.
+    pub(super) scope_storage: Option<StorageType>,
 }
 
 impl ReplicatedArchetype {
@@ -115,6 +129,7 @@ impl ReplicatedArchetype {
         Self {
             id,
             components: Default::default(),
+            scope_storage: None,
         }
     }
 }
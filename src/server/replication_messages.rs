@@ -44,4 +44,15 @@ impl ReplicationMessages {
     pub(super) fn iter_mut(&mut self) -> impl Iterator<Item = &mut (UpdateMessage, MutateMessage)> {
         self.messages.iter_mut().take(self.len)
     }
+
+    /// Returns mutable chunks of messages with the specified size.
+    ///
+    /// Chunks match the clients from [`ReplicatedClients::chunks_mut`](crate::core::replication::replicated_clients::ReplicatedClients::chunks_mut)
+    /// with the same size.
+    pub(super) fn chunks_mut(
+        &mut self,
+        chunk_size: usize,
+    ) -> impl Iterator<Item = &mut [(UpdateMessage, MutateMessage)]> {
+        self.messages[..self.len].chunks_mut(chunk_size)
+    }
 }
@@ -33,6 +33,16 @@ impl ComponentChanges {
         self.components.push(component);
     }
 
+    /// Moves all ranges by `offset`.
+    pub(super) fn shift(&mut self, offset: usize) {
+        self.entity.start += offset;
+        self.entity.end += offset;
+        for component in &mut self.components {
+            component.start += offset;
+            component.end += offset;
+        }
+    }
+
     pub(super) fn extend(&mut self, other: &Self) {
         self.components.extend(other.components.iter().cloned());
         self.components_len += other.components_len;
@@ -1,14 +1,20 @@
-use std::{io::Cursor, mem, ops::Range, time::Duration};
+use std::{mem, ops::Range, time::Duration};
 
 use bevy::{ecs::component::Tick, prelude::*};
 use integer_encoding::{VarInt, VarIntWriter};
 
-use super::{component_changes::ComponentChanges, serialized_data::SerializedData};
-use crate::core::{
-    channels::ReplicationChannel,
-    replication::replicated_clients::{ClientBuffers, ReplicatedClient},
-    replicon_server::RepliconServer,
-    replicon_tick::RepliconTick,
+use super::{
+    component_changes::ComponentChanges,
+    serialized_data::{self, SerializedData},
+};
+use crate::{
+    core::{
+        channels::ReplicationChannel,
+        replication::replicated_clients::{ClientBuffers, ReplicatedClient},
+        replicon_server::RepliconServer,
+        replicon_tick::RepliconTick,
+    },
+    server::ReplicatedClientStats,
 };
 
 /// A message with replicated component mutations.
@@ -16,6 +22,10 @@ use crate::core::{
 /// Contains update tick, current tick, mutate index and component mutations since
 /// the last acknowledged tick for each entity.
 ///
+/// Ticks are serialized as a header varint followed by the update tick. The header contains
+/// the delta between the current tick and the update tick shifted left by one bit. The lowest bit
+/// indicates if the update tick is serialized as varint or fixint, see [`serialized_data::write_tick`].
+///
 /// Cannot be applied on the client until the update message matching this message's update tick
 /// has been applied to the client world.
 /// The message will be manually split into packets up to max size, and each packet will be applied
@@ -113,6 +123,15 @@ impl MutateMessage {
         }
     }
 
+    /// Moves ranges of all mutations by `offset`.
+    ///
+    /// See also [`UpdateMessage::shift_changes`](super::update_message::UpdateMessage::shift_changes).
+    pub(crate) fn shift_mutations(&mut self, offset: usize) {
+        for mutations in &mut self.mutations {
+            mutations.shift(offset);
+        }
+    }
+
     pub(crate) fn is_empty(&self) -> bool {
         self.mutations.is_empty()
     }
@@ -124,16 +143,22 @@ impl MutateMessage {
         client_buffers: &mut ClientBuffers,
         serialized: &SerializedData,
         track_mutate_messages: bool,
-        server_tick: Range<usize>,
+        server_tick: RepliconTick,
         tick: Tick,
         timestamp: Duration,
+        mut stats: Option<&mut ReplicatedClientStats>,
     ) -> bincode::Result<usize> {
         debug_assert_eq!(self.entities.len(), self.mutations.len());
 
         const MAX_COUNT_SIZE: usize = mem::size_of::<usize>() + 1;
-        let mut update_tick = Cursor::new([0; mem::size_of::<RepliconTick>()]);
-        bincode::serialize_into(&mut update_tick, &client.update_tick())?;
-        let mut metadata_size = update_tick.get_ref().len() + server_tick.len();
+        const MAX_TICKS_SIZE: usize = 2 * (mem::size_of::<RepliconTick>() + 1);
+        let mut ticks = Vec::with_capacity(MAX_TICKS_SIZE);
+        let update_tick = client.update_tick();
+        let tick_delta = (server_tick - update_tick) as u64;
+        let varint_bit = serialized_data::is_varint_tick(update_tick) as u64;
+        ticks.write_varint(tick_delta << 1 | varint_bit)?;
+        serialized_data::write_tick(&mut ticks, update_tick)?;
+        let mut metadata_size = ticks.len();
         if track_mutate_messages {
             metadata_size += MAX_COUNT_SIZE;
         }
@@ -188,8 +213,7 @@ impl MutateMessage {
             }
             let mut message = Vec::with_capacity(message_size);
 
-            message.extend_from_slice(update_tick.get_ref());
-            message.extend_from_slice(&serialized[server_tick.clone()]);
+            message.extend_from_slice(&ticks);
             if track_mutate_messages {
                 message.write_varint(messages_count)?;
             }
@@ -204,6 +228,17 @@ impl MutateMessage {
 
             debug_assert_eq!(message.len(), message_size);
 
+            if let Some(stats) = &mut stats {
+                let mutations = &self.mutations[mutations_range];
+                stats.entities += mutations.len();
+                stats.components += mutations
+                    .iter()
+                    .map(|mutations| mutations.components_len)
+                    .sum::<usize>();
+                stats.mutate_messages += 1;
+                stats.mutate_bytes += message.len();
+            }
+
             server.send(client.id(), ReplicationChannel::Mutations, message);
         }
 
@@ -1,7 +1,8 @@
-use std::ops::Range;
+use std::{mem, ops::Range};
 
 use bevy::{prelude::*, ptr::Ptr};
 use bincode::{DefaultOptions, Options};
+use integer_encoding::{VarInt, VarIntWriter};
 
 use crate::{
     core::{
@@ -22,22 +23,42 @@ use crate::{
 pub(crate) struct SerializedData(Vec<u8>);
 
 impl SerializedData {
-    pub(crate) fn write_mappings(
+    pub(crate) fn write_mapping(
         &mut self,
-        mappings: impl Iterator<Item = ClientMapping>,
+        mapping: ClientMapping,
     ) -> bincode::Result<Range<usize>> {
         let start = self.len();
 
-        for mapping in mappings {
-            self.write_entity(mapping.server_entity)?;
-            self.write_entity(mapping.client_entity)?;
-        }
+        self.write_entity(mapping.server_entity)?;
+        self.write_entity(mapping.client_entity)?;
 
         let end = self.len();
 
         Ok(start..end)
     }
 
+    /// Writes client entities that won't be mapped, each followed by a byte that indicates
+    /// if the mapping was dropped (`1`) or rejected (`0`).
+    ///
+    /// Returns the written range and the number of entities.
+    pub(crate) fn write_unmapped(
+        &mut self,
+        entities: impl Iterator<Item = (Entity, bool)>,
+    ) -> bincode::Result<(Range<usize>, usize)> {
+        let start = self.len();
+
+        let mut len = 0;
+        for (entity, dropped) in entities {
+            self.write_entity(entity)?;
+            self.0.push(dropped.into());
+            len += 1;
+        }
+
+        let end = self.len();
+
+        Ok((start..end, len))
+    }
+
     pub(crate) fn write_fn_ids(
         &mut self,
         fn_ids: impl Iterator<Item = FnsId>,
@@ -88,17 +109,37 @@ impl SerializedData {
         Ok(start..end)
     }
 
+    /// Serializes `tick` using [`write_tick`].
+    ///
+    /// The used encoding can be determined by [`is_varint_tick`].
     pub(crate) fn write_tick(&mut self, tick: RepliconTick) -> bincode::Result<Range<usize>> {
         let start = self.len();
 
-        // Use fixedint encoding as serializing ticks as varints increases the average message size.
-        // A tick >= 2^16 will be 5 bytes: https://docs.rs/bincode/1.3.3/bincode/config/struct.VarintEncoding.html.
-        // At 60 ticks/sec, that will happen after 18 minutes.
-        // So any session over 36 minutes would transmit more total bytes with varint encoding.
-        // TODO: consider dynamically switching from varint to fixint encoding using one of the `UpdateMessageFlags` when tick sizes get large enough.
-        bincode::serialize_into(&mut self.0, &tick)?;
+        write_tick(&mut self.0, tick)?;
+
         let end = self.len();
 
         Ok(start..end)
     }
 }
+
+/// Serializes `tick` as varint if it takes fewer bytes than fixint, otherwise as fixint.
+///
+/// A varint tick takes less than 4 bytes until 2^21. At 60 ticks/sec, that will happen
+/// after ~9.7 hours, so most sessions will never switch to fixint.
+///
+/// Returns `true` if the tick was written as varint.
+pub(crate) fn write_tick(message: &mut Vec<u8>, tick: RepliconTick) -> bincode::Result<bool> {
+    if is_varint_tick(tick) {
+        message.write_varint(tick.get())?;
+        Ok(true)
+    } else {
+        bincode::serialize_into(message, &tick)?;
+        Ok(false)
+    }
+}
+
+/// Returns `true` if [`write_tick`] will serialize `tick` as varint.
+pub(crate) fn is_varint_tick(tick: RepliconTick) -> bool {
+    tick.get().required_space() < mem::size_of::<RepliconTick>()
+}
@@ -5,21 +5,26 @@ use bevy::prelude::*;
 use integer_encoding::{FixedIntWriter, VarInt, VarIntWriter};
 
 use super::{
-    component_changes::ComponentChanges, mutate_message::MutateMessage,
-    serialized_data::SerializedData,
+    component_changes::ComponentChanges,
+    mutate_message::MutateMessage,
+    serialized_data::{self, SerializedData},
 };
-use crate::core::{
-    channels::ReplicationChannel,
-    replication::{
-        replicated_clients::{client_visibility::Visibility, ReplicatedClient},
-        update_message_flags::UpdateMessageFlags,
+use crate::{
+    core::{
+        channels::ReplicationChannel,
+        replication::{
+            replicated_clients::{client_visibility::Visibility, ReplicatedClient},
+            update_message_flags::UpdateMessageFlags,
+        },
+        replicon_server::RepliconServer,
+        replicon_tick::RepliconTick,
     },
-    replicon_server::RepliconServer,
+    server::ReplicatedClientStats,
 };
 
 /// A message with replicated data.
 ///
-/// Contains tick, mappings, insertions, removals, and despawns that
+/// Contains tick, mappings, unmapped client entities, insertions, removals, and despawns that
 /// happened in this tick.
 ///
 /// The data is serialized manually and stored in the form of ranges
@@ -28,6 +33,8 @@ use crate::core::{
 /// Sent over [`ReplicationChannel::Updates`] channel.
 ///
 /// Some data is optional, and their presence is encoded in the [`UpdateMessageFlags`] bitset.
+/// The bitset also indicates if the tick is serialized as varint or fixint,
+/// see [`serialized_data::write_tick`] for details.
 ///
 /// To know how much data array takes, we serialize it's length. We use `usize`,
 /// but we use variable integer encoding, so they are correctly deserialized even
@@ -53,6 +60,17 @@ pub(crate) struct UpdateMessage {
     /// Number of pairs encoded in [`Self::mappings`].
     mappings_len: usize,
 
+    /// Client's pre-spawned entities that won't be mapped.
+    ///
+    /// Serialized as single continuous chunk of entities, each followed by a byte
+    /// that indicates if the mapping was dropped or rejected.
+    ///
+    /// See also [`SerializedData::write_unmapped`].
+    unmapped: Range<usize>,
+
+    /// Number of entities encoded in [`Self::unmapped`].
+    unmapped_len: usize,
+
     /// Despawns that happened in this tick.
     ///
     /// Since clients may see different entities, it's serialized as multiple chunks of entities.
@@ -92,6 +110,11 @@ pub(crate) struct UpdateMessage {
 
     /// Intermediate buffer to reuse allocated memory from [`Self::changes`].
     buffer: Vec<Vec<Range<usize>>>,
+
+    /// Indicates that the message contains all visible entities and should replace the client state.
+    ///
+    /// Such message is sent even if it's empty.
+    resync: bool,
 }
 
 impl UpdateMessage {
@@ -100,6 +123,18 @@ impl UpdateMessage {
         self.mappings_len = len;
     }
 
+    pub(crate) fn set_unmapped(&mut self, unmapped: Range<usize>, len: usize) {
+        self.unmapped = unmapped;
+        self.unmapped_len = len;
+    }
+
+    /// Marks the message as a resync.
+    ///
+    /// See [`ReplicatedClient::request_resync`].
+    pub(crate) fn set_resync(&mut self) {
+        self.resync = true;
+    }
+
     pub(crate) fn add_despawn(&mut self, entity: Range<usize>) {
         self.despawns_len += 1;
         if let Some(last) = self.despawns.last_mut() {
@@ -192,11 +227,23 @@ impl UpdateMessage {
         mutate_message.pop_mutations();
     }
 
+    /// Moves ranges of all component changes by `offset`.
+    ///
+    /// Used when changes were serialized into a separate [`SerializedData`]
+    /// that was appended to the main one.
+    pub(crate) fn shift_changes(&mut self, offset: usize) {
+        for changes in &mut self.changes {
+            changes.shift(offset);
+        }
+    }
+
     pub(crate) fn is_empty(&self) -> bool {
         self.changes.is_empty()
             && self.despawns.is_empty()
             && self.removals.is_empty()
             && self.mappings.is_empty()
+            && self.unmapped.is_empty()
+            && !self.resync
     }
 
     pub(crate) fn send(
@@ -204,13 +251,15 @@ impl UpdateMessage {
         server: &mut RepliconServer,
         client: &ReplicatedClient,
         serialized: &SerializedData,
-        server_tick: Range<usize>,
+        server_tick: RepliconTick,
+        tick_range: Range<usize>,
+        stats: Option<&mut ReplicatedClientStats>,
     ) -> bincode::Result<()> {
         let flags = self.flags();
         let last_flag = flags.last();
 
         // Precalculate size first to avoid extra allocations.
-        let mut message_size = size_of::<UpdateMessageFlags>() + server_tick.len();
+        let mut message_size = size_of::<UpdateMessageFlags>() + tick_range.len();
         for (_, flag) in flags.iter_names() {
             match flag {
                 UpdateMessageFlags::MAPPINGS => {
@@ -219,6 +268,12 @@ impl UpdateMessage {
                     }
                     message_size += self.mappings.len();
                 }
+                UpdateMessageFlags::UNMAPPED => {
+                    if flag != last_flag {
+                        message_size += self.unmapped_len.required_space();
+                    }
+                    message_size += self.unmapped.len();
+                }
                 UpdateMessageFlags::DESPAWNS => {
                     if flag != last_flag {
                         message_size += self.despawns_len.required_space();
@@ -252,8 +307,15 @@ impl UpdateMessage {
         }
 
         let mut message = Vec::with_capacity(message_size);
-        message.write_fixedint(flags.bits())?;
-        message.extend_from_slice(&serialized[server_tick]);
+        let mut header_flags = flags;
+        if serialized_data::is_varint_tick(server_tick) {
+            header_flags.insert(UpdateMessageFlags::VARINT_TICK);
+        }
+        if self.resync {
+            header_flags.insert(UpdateMessageFlags::RESYNC);
+        }
+        message.write_fixedint(header_flags.bits())?;
+        message.extend_from_slice(&serialized[tick_range]);
         for (_, flag) in flags.iter_names() {
             match flag {
                 UpdateMessageFlags::MAPPINGS => {
@@ -270,6 +332,12 @@ impl UpdateMessage {
                     message.write_varint(self.mappings_len)?;
                     message.extend_from_slice(&serialized[self.mappings.clone()]);
                 }
+                UpdateMessageFlags::UNMAPPED => {
+                    if flag != last_flag {
+                        message.write_varint(self.unmapped_len)?;
+                    }
+                    message.extend_from_slice(&serialized[self.unmapped.clone()]);
+                }
                 UpdateMessageFlags::DESPAWNS => {
                     if flag != last_flag {
                         message.write_varint(self.despawns_len)?;
@@ -304,6 +372,17 @@ impl UpdateMessage {
 
         debug_assert_eq!(message.len(), message_size);
 
+        if let Some(stats) = stats {
+            stats.entities += self.changes.len();
+            stats.components += self
+                .changes
+                .iter()
+                .map(|changes| changes.components_len)
+                .sum::<usize>();
+            stats.update_messages += 1;
+            stats.update_bytes += message.len();
+        }
+
         server.send(client.id(), ReplicationChannel::Updates, message);
 
         Ok(())
@@ -315,6 +394,9 @@ impl UpdateMessage {
         if !self.mappings.is_empty() {
             flags |= UpdateMessageFlags::MAPPINGS;
         }
+        if !self.unmapped.is_empty() {
+            flags |= UpdateMessageFlags::UNMAPPED;
+        }
         if !self.despawns.is_empty() {
             flags |= UpdateMessageFlags::DESPAWNS;
         }
@@ -334,9 +416,12 @@ impl UpdateMessage {
     pub(super) fn clear(&mut self) {
         self.mappings = Default::default();
         self.mappings_len = 0;
+        self.unmapped = Default::default();
+        self.unmapped_len = 0;
         self.despawns.clear();
         self.despawns_len = 0;
         self.removals.clear();
+        self.resync = false;
         self.buffer
             .extend(self.changes.drain(..).map(|mut changes| {
                 changes.components.clear();
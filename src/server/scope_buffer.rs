@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use super::{ServerPlugin, ServerSet};
+use crate::core::{
+    common_conditions::server_running,
+    replication::{replication_scope::ReplicationScope, Replicated},
+};
+
+/// Stores entities with changed [`ReplicationScope`] into [`ScopeBuffer`] resource.
+///
+/// Used to avoid missing changes in case the server's tick policy is not [`TickPolicy::EveryFrame`](super::TickPolicy::EveryFrame).
+pub(super) struct ScopeBufferPlugin;
+
+impl Plugin for ScopeBufferPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScopeBuffer>().add_systems(
+            PostUpdate,
+            Self::buffer_scopes
+                .before(ServerPlugin::send_replication)
+                .in_set(ServerSet::Send)
+                .run_if(server_running),
+        );
+    }
+}
+
+impl ScopeBufferPlugin {
+    fn buffer_scopes(
+        changed_scopes: Query<(Entity, Ref<Replicated>), Changed<ReplicationScope>>,
+        mut scope_buffer: ResMut<ScopeBuffer>,
+    ) {
+        // New entities can't be replicated to any client yet.
+        let changed = changed_scopes
+            .iter()
+            .filter(|(_, replicated)| !replicated.is_added())
+            .map(|(entity, _)| entity);
+        scope_buffer.extend(changed);
+    }
+}
+
+/// Buffer with all entities whose [`ReplicationScope`] was changed.
+///
+/// Should be cleaned up manually.
+#[derive(Default, Resource, Deref, DerefMut)]
+pub(crate) struct ScopeBuffer(Vec<Entity>);
@@ -0,0 +1,94 @@
+use bevy::utils::HashMap;
+
+use crate::core::replication::{
+    replicated_clients::ReplicatedClient, replication_scope::ReplicationScope,
+};
+
+/// Indices of clients that can receive entities from each [`ReplicationScope`].
+///
+/// Built once per tick to iterate only over clients that can see a scoped entity
+/// instead of checking scopes for every client.
+pub(super) struct ScopeClients {
+    all: Vec<usize>,
+    scopes: HashMap<ReplicationScope, Vec<usize>>,
+}
+
+impl ScopeClients {
+    /// Collects indices for the specified clients.
+    pub(super) fn new(clients: &[ReplicatedClient]) -> Self {
+        let mut scopes = HashMap::<_, Vec<_>>::default();
+        for (index, client) in clients.iter().enumerate() {
+            for scope in client.scopes() {
+                scopes.entry(scope).or_default().push(index);
+            }
+        }
+
+        Self {
+            all: (0..clients.len()).collect(),
+            scopes,
+        }
+    }
+
+    /// Returns `true` if none of the clients are assigned to any scope.
+    pub(super) fn is_empty(&self) -> bool {
+        self.scopes.is_empty()
+    }
+
+    /// Returns indices of clients that can receive an entity with the specified scope.
+    ///
+    /// Entities without a scope can be received by all clients.
+    pub(super) fn get(&self, scope: Option<ReplicationScope>) -> &[usize] {
+        match scope {
+            Some(scope) => self.scopes.get(&scope).map_or(&[], Vec::as_slice),
+            None => &self.all,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        replication::replicated_clients::{ClientBuffers, ReplicatedClients, VisibilityPolicy},
+        ClientId,
+    };
+
+    #[test]
+    fn indices() {
+        let mut replicated_clients = ReplicatedClients::new(VisibilityPolicy::All, true);
+        let mut client_buffers = ClientBuffers::default();
+        for id in 0..3 {
+            replicated_clients.add(&mut client_buffers, ClientId::new(id));
+        }
+
+        let first = ReplicationScope(0);
+        let second = ReplicationScope(1);
+        replicated_clients
+            .client_mut(ClientId::new(0))
+            .add_scope(first);
+        let client = replicated_clients.client_mut(ClientId::new(2));
+        client.add_scope(first);
+        client.add_scope(second);
+
+        let clients = replicated_clients.chunks_mut(3).next().unwrap();
+        let scope_clients = ScopeClients::new(clients);
+        assert!(!scope_clients.is_empty());
+        assert_eq!(scope_clients.get(None), [0, 1, 2]);
+        assert_eq!(scope_clients.get(Some(first)), [0, 2]);
+        assert_eq!(scope_clients.get(Some(second)), [2]);
+        assert!(scope_clients.get(Some(ReplicationScope(2))).is_empty());
+    }
+
+    #[test]
+    fn without_scopes() {
+        let mut replicated_clients = ReplicatedClients::new(VisibilityPolicy::All, true);
+        let mut client_buffers = ClientBuffers::default();
+        replicated_clients.add(&mut client_buffers, ClientId::new(0));
+
+        let clients = replicated_clients.chunks_mut(1).next().unwrap();
+        let scope_clients = ScopeClients::new(clients);
+        assert!(scope_clients.is_empty());
+        assert_eq!(scope_clients.get(None), [0]);
+        assert!(scope_clients.get(Some(ReplicationScope(0))).is_empty());
+    }
+}
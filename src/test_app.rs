@@ -76,6 +76,17 @@ pub trait ServerTestAppExt {
     /// Panics if a client app hasn't been connected before.
     fn disconnect_client(&mut self, client_app: &mut App);
 
+    /// Like [`Self::disconnect_client`], but simulates a disconnect initiated by the server.
+    ///
+    /// Uses the reason from [`RepliconServer::disconnect`] if it was requested for this client
+    /// or requests a disconnect with a default reason otherwise.
+    /// Pending messages, including the reason, are delivered to the client before disconnecting.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a client app hasn't been connected before.
+    fn disconnect_client_by_server(&mut self, client_app: &mut App);
+
     /// Exchanges messages between client and server.
     ///
     /// Internally updates [`self`] before sending and updates the client app after receiving.
@@ -134,6 +145,34 @@ impl ServerTestAppExt for App {
         client_app.update();
     }
 
+    fn disconnect_client_by_server(&mut self, client_app: &mut App) {
+        let client = client_app.world().resource::<RepliconClient>();
+        let client_id = client
+            .id()
+            .expect("client should have an assigned ID for disconnect");
+
+        let mut server = self.world_mut().resource_mut::<RepliconServer>();
+        if !server.is_disconnecting(client_id) {
+            server.disconnect(client_id, "Disconnected by server");
+        }
+
+        self.exchange_with_client(client_app);
+
+        let mut server = self.world_mut().resource_mut::<RepliconServer>();
+        let reason = server
+            .take_disconnect(client_id)
+            .expect("disconnect should be requested for the client");
+
+        let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+        client.set_status(RepliconClientStatus::Disconnected);
+
+        self.world_mut()
+            .trigger(ServerEvent::ClientDisconnected { client_id, reason });
+
+        self.update();
+        client_app.update();
+    }
+
     fn exchange_with_client(&mut self, client_app: &mut App) {
         let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
         let client_id = client
@@ -145,13 +184,8 @@ impl ServerTestAppExt for App {
             server.insert_received(client_id, channel_id, message)
         }
 
-        server.retain_sent(|(sender_id, channel_id, message)| {
-            if *sender_id == client_id {
-                client.insert_received(*channel_id, message.clone());
-                false
-            } else {
-                true
-            }
-        })
+        for (channel_id, message) in server.drain_sent_for(client_id) {
+            client.insert_received(channel_id, message);
+        }
     }
 }
@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn owned() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            OwnershipPlugin,
+        ))
+        .replicate::<DummyComponent>();
+    }
+    client_app.init_resource::<OwnerRuns>().add_systems(
+        Update,
+        (|mut runs: ResMut<OwnerRuns>| runs.0 += 1).run_if(owns_any_with::<DummyComponent>),
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, Owner(client_id)));
+    server_app
+        .world_mut()
+        .spawn((Replicated, Owner(ClientId::SERVER)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut owned = client_app
+        .world_mut()
+        .query_filtered::<&Owner, With<Owned>>();
+    let owner = owned.single(client_app.world());
+    assert_eq!(**owner, client_id);
+    assert_eq!(client_app.world().resource::<OwnerRuns>().0, 1);
+
+    let mut owned = server_app
+        .world_mut()
+        .query_filtered::<&Owner, With<Owned>>();
+    let owner = owned.single(server_app.world());
+    assert_eq!(
+        **owner,
+        ClientId::SERVER,
+        "server should own its entities locally"
+    );
+}
+
+#[test]
+fn transfer() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            OwnershipPlugin,
+        ));
+    }
+    server_app.init_resource::<Transfers>().add_observer(
+        |trigger: Trigger<OwnershipTransferred>, mut transfers: ResMut<Transfers>| {
+            transfers.0.push(*trigger.event());
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+    server_app
+        .world_mut()
+        .commands()
+        .entity(server_entity)
+        .transfer_ownership(client_id);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut owned = client_app.world_mut().query::<&Owned>();
+    assert_eq!(owned.iter(client_app.world()).count(), 1);
+
+    server_app
+        .world_mut()
+        .commands()
+        .entity(server_entity)
+        .revoke_ownership();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        owned.iter(client_app.world()).count(),
+        0,
+        "marker should be removed with owner"
+    );
+
+    let transfers = server_app.world().resource::<Transfers>();
+    assert_eq!(
+        transfers.0,
+        [
+            OwnershipTransferred {
+                entity: server_entity,
+                previous: None,
+                owner: Some(client_id),
+            },
+            OwnershipTransferred {
+                entity: server_entity,
+                previous: Some(client_id),
+                owner: None,
+            }
+        ]
+    );
+}
+
+#[test]
+fn disconnect_despawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            OwnershipPlugin,
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let owned_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Owner(client_id)))
+        .id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Owner(ClientId::SERVER)))
+        .id();
+
+    server_app.disconnect_client(&mut client_app);
+
+    assert!(server_app.world().get_entity(owned_entity).is_err());
+    assert!(server_app.world().get_entity(server_entity).is_ok());
+}
+
+#[test]
+fn disconnect_remove_owner() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            OwnershipPlugin,
+        ));
+    }
+    server_app.insert_resource(OwnerDisconnectPolicy::RemoveOwner);
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Owner(client_id)))
+        .id();
+
+    server_app.disconnect_client(&mut client_app);
+
+    let server_entity = server_app.world().entity(server_entity);
+    assert!(!server_entity.contains::<Owner>());
+}
+
+#[test]
+fn disconnect_keep() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            OwnershipPlugin,
+        ));
+    }
+    server_app.insert_resource(OwnerDisconnectPolicy::Keep);
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Owner(client_id)))
+        .id();
+
+    server_app.disconnect_client(&mut client_app);
+
+    let server_entity = server_app.world().entity(server_entity);
+    assert_eq!(server_entity.get::<Owner>(), Some(&Owner(client_id)));
+}
+
+#[derive(Resource, Default)]
+struct OwnerRuns(usize);
+
+#[derive(Resource, Default)]
+struct Transfers(Vec<OwnershipTransferred>);
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
@@ -120,9 +120,190 @@ fn local_resending() {
     assert_eq!(client_events.len(), 1);
 }
 
+#[test]
+fn size_limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<SizedEvent>(ChannelKind::Ordered);
+    }
+
+    server_app
+        .world_mut()
+        .resource_mut::<ClientEventLimiter>()
+        .set_limits::<SizedEvent>(ClientEventLimits {
+            max_size: Some(8),
+            ..Default::default()
+        });
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world_mut().send_event(SizedEvent(vec![0; 4]));
+    client_app.world_mut().send_event(SizedEvent(vec![0; 16]));
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<SizedEvent>>>();
+    assert_eq!(client_events.len(), 1);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let exceeded: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Events<ClientEventLimitExceeded>>()
+        .drain()
+        .collect();
+    let [exceeded] = exceeded.as_slice() else {
+        panic!("server should report a single violation");
+    };
+    assert_eq!(exceeded.client_id, client_id);
+    assert!(matches!(
+        exceeded.violation,
+        LimitViolation::Size { max_size: 8, .. }
+    ));
+}
+
+#[test]
+fn per_update_limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app
+        .world_mut()
+        .resource_mut::<ClientEventLimiter>()
+        .set_limits::<DummyEvent>(ClientEventLimits {
+            max_per_update: Some(2),
+            ..Default::default()
+        });
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+        .world_mut()
+        .send_event_batch([DummyEvent, DummyEvent, DummyEvent]);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(client_events.len(), 2);
+
+    let exceeded = server_app
+        .world()
+        .resource::<Events<ClientEventLimitExceeded>>();
+    assert_eq!(exceeded.len(), 1);
+}
+
+#[test]
+fn rate_limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app
+        .world_mut()
+        .resource_mut::<ClientEventLimiter>()
+        .set_limits::<DummyEvent>(ClientEventLimits {
+            max_per_second: Some(2),
+            ..Default::default()
+        });
+
+    server_app.connect_client(&mut client_app);
+
+    for _ in 0..2 {
+        client_app
+            .world_mut()
+            .send_event_batch([DummyEvent, DummyEvent]);
+
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        server_app.update();
+    }
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(
+        client_events.len(),
+        2,
+        "events beyond the rate should be discarded"
+    );
+
+    let exceeded = server_app
+        .world()
+        .resource::<Events<ClientEventLimitExceeded>>();
+    assert!(exceeded
+        .iter_current_update_events()
+        .all(|exceeded| exceeded.violation == LimitViolation::Rate { max_per_second: 2 }));
+    assert_eq!(exceeded.len(), 2);
+}
+
+#[test]
+fn client_limits() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut limiter = server_app.world_mut().resource_mut::<ClientEventLimiter>();
+    limiter.set_limits::<DummyEvent>(ClientEventLimits {
+        max_per_update: Some(1),
+        ..Default::default()
+    });
+    limiter.set_client_limits::<DummyEvent>(client_id, ClientEventLimits::default());
+
+    client_app
+        .world_mut()
+        .send_event_batch([DummyEvent, DummyEvent]);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client_events = server_app
+        .world()
+        .resource::<Events<FromClient<DummyEvent>>>();
+    assert_eq!(
+        client_events.len(),
+        2,
+        "client-specific limits should override limits for the event"
+    );
+
+    let exceeded = server_app
+        .world()
+        .resource::<Events<ClientEventLimitExceeded>>();
+    assert!(exceeded.is_empty());
+}
+
 #[derive(Deserialize, Event, Serialize)]
 struct DummyEvent;
 
+#[derive(Deserialize, Event, Serialize)]
+struct SizedEvent(Vec<u8>);
+
 #[derive(Deserialize, Event, Serialize, Clone)]
 struct MappedEvent(Entity);
 
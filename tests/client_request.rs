@@ -0,0 +1,263 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{event::Events, system::RunSystemOnce},
+    prelude::*,
+};
+use bevy_replicon::{prelude::*, test_app::ServerTestAppExt};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn response() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_client_request::<DummyRequest, DummyResponse>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let id = client_app
+        .world_mut()
+        .run_system_once(|mut requests: RequestSender<DummyRequest>| requests.send(DummyRequest))
+        .unwrap();
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let responses: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Events<FromClient<Request<DummyRequest>>>>()
+        .drain()
+        .map(|request| request.respond(DummyResponse(id)))
+        .collect();
+    assert_eq!(responses.len(), 1);
+    server_app.world_mut().send_event_batch(responses);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let outcomes: Vec<_> = client_app
+        .world_mut()
+        .resource_mut::<Events<RequestOutcome<DummyRequest, DummyResponse>>>()
+        .drain()
+        .collect();
+    let [outcome] = outcomes.as_slice() else {
+        panic!("client should receive a single outcome");
+    };
+    assert_eq!(outcome.id, id);
+    assert_eq!(outcome.response, Some(DummyResponse(id)));
+
+    let pending = client_app
+        .world()
+        .resource::<PendingRequests<DummyRequest>>();
+    assert!(pending.is_empty());
+}
+
+#[test]
+fn timeout() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_client_request::<DummyRequest, DummyResponse>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+        .world_mut()
+        .resource_mut::<PendingRequests<DummyRequest>>()
+        .set_timeout(Duration::ZERO);
+
+    let id = client_app
+        .world_mut()
+        .run_system_once(|mut requests: RequestSender<DummyRequest>| requests.send(DummyRequest))
+        .unwrap();
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let responses: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Events<FromClient<Request<DummyRequest>>>>()
+        .drain()
+        .map(|request| request.respond(DummyResponse(id)))
+        .collect();
+    server_app.world_mut().send_event_batch(responses);
+
+    let outcomes: Vec<_> = client_app
+        .world_mut()
+        .resource_mut::<Events<RequestOutcome<DummyRequest, DummyResponse>>>()
+        .drain()
+        .collect();
+    let [outcome] = outcomes.as_slice() else {
+        panic!("request should time out");
+    };
+    assert_eq!(outcome.id, id);
+    assert_eq!(outcome.response, None);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let outcomes = client_app
+        .world()
+        .resource::<Events<RequestOutcome<DummyRequest, DummyResponse>>>();
+    assert!(
+        outcomes.is_empty(),
+        "response after timeout should be ignored"
+    );
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_client_request::<DummyRequest, DummyResponse>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let id = client_app
+        .world_mut()
+        .run_system_once(|mut requests: RequestSender<DummyRequest>| requests.send(DummyRequest))
+        .unwrap();
+
+    client_app.update();
+    server_app.disconnect_client(&mut client_app);
+
+    let outcomes: Vec<_> = client_app
+        .world_mut()
+        .resource_mut::<Events<RequestOutcome<DummyRequest, DummyResponse>>>()
+        .drain()
+        .collect();
+    let [outcome] = outcomes.as_slice() else {
+        panic!("client should receive a single outcome");
+    };
+    assert_eq!(outcome.id, id);
+    assert_eq!(
+        outcome.response, None,
+        "request should be discarded on disconnect"
+    );
+
+    let pending = client_app
+        .world()
+        .resource::<PendingRequests<DummyRequest>>();
+    assert!(pending.is_empty());
+}
+
+#[test]
+fn local_response() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_client_request::<DummyRequest, DummyResponse>(ChannelKind::Ordered);
+
+    app.world_mut()
+        .resource_mut::<RepliconServer>()
+        .set_running(true);
+
+    let id = app
+        .world_mut()
+        .run_system_once(|mut requests: RequestSender<DummyRequest>| requests.send(DummyRequest))
+        .unwrap();
+
+    app.update();
+
+    let responses: Vec<_> = app
+        .world_mut()
+        .resource_mut::<Events<FromClient<Request<DummyRequest>>>>()
+        .drain()
+        .map(|request| {
+            assert_eq!(request.client_id, ClientId::SERVER);
+            request.respond(DummyResponse(id))
+        })
+        .collect();
+    assert_eq!(responses.len(), 1);
+    app.world_mut().send_event_batch(responses);
+
+    app.update();
+    app.update();
+
+    let outcomes: Vec<_> = app
+        .world_mut()
+        .resource_mut::<Events<RequestOutcome<DummyRequest, DummyResponse>>>()
+        .drain()
+        .collect();
+    let [outcome] = outcomes.as_slice() else {
+        panic!("server should receive its own response");
+    };
+    assert_eq!(outcome.response, Some(DummyResponse(id)));
+}
+
+#[test]
+fn limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_request::<DummyRequest, DummyResponse>(ChannelKind::Ordered);
+    }
+
+    server_app
+        .world_mut()
+        .resource_mut::<ClientEventLimiter>()
+        .set_limits::<DummyRequest>(ClientEventLimits {
+            max_per_update: Some(1),
+            ..Default::default()
+        });
+
+    server_app.connect_client(&mut client_app);
+
+    client_app
+        .world_mut()
+        .run_system_once(|mut requests: RequestSender<DummyRequest>| {
+            requests.send(DummyRequest);
+            requests.send(DummyRequest);
+        })
+        .unwrap();
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let requests = server_app
+        .world()
+        .resource::<Events<FromClient<Request<DummyRequest>>>>();
+    assert_eq!(requests.len(), 1);
+
+    let exceeded = server_app
+        .world()
+        .resource::<Events<ClientEventLimitExceeded>>();
+    assert_eq!(exceeded.len(), 1);
+}
+
+#[derive(Event, Deserialize, Serialize)]
+struct DummyRequest;
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+struct DummyResponse(RequestId);
@@ -0,0 +1,201 @@
+use std::any;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_trigger::<DummyEvent>(ChannelKind::Ordered);
+    }
+    server_app.init_resource::<TriggerReader>();
+    server_app.add_observer(
+        |trigger: Trigger<FromClient<DummyEvent>>, mut reader: ResMut<TriggerReader>| {
+            reader.events.push((trigger.client_id, trigger.entity()));
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.world_mut().commands().client_trigger(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let reader = server_app.world().resource::<TriggerReader>();
+    assert_eq!(reader.events, [(client_id, Entity::PLACEHOLDER)]);
+}
+
+#[test]
+fn mapping_and_sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_trigger::<DummyEvent>(ChannelKind::Ordered);
+    }
+    server_app.init_resource::<TriggerReader>();
+    server_app.add_observer(
+        |trigger: Trigger<FromClient<DummyEvent>>, mut reader: ResMut<TriggerReader>| {
+            reader.events.push((trigger.client_id, trigger.entity()));
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn_empty().id();
+    let client_entity = client_app.world_mut().spawn_empty().id();
+    client_app
+        .world_mut()
+        .resource_mut::<ServerEntityMap>()
+        .insert(server_entity, client_entity);
+
+    client_app
+        .world_mut()
+        .commands()
+        .client_trigger_targets(DummyEvent, client_entity);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let reader = server_app.world().resource::<TriggerReader>();
+    assert_eq!(reader.events, [(client_id, server_entity)]);
+}
+
+#[test]
+fn unmapped_target() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_trigger::<DummyEvent>(ChannelKind::Ordered);
+    }
+    server_app.init_resource::<TriggerReader>();
+    server_app.add_observer(
+        |trigger: Trigger<FromClient<DummyEvent>>, mut reader: ResMut<TriggerReader>| {
+            reader.events.push((trigger.client_id, trigger.entity()));
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn_empty().id();
+    let client_entity = client_app.world_mut().spawn_empty().id();
+    let local_entity = client_app.world_mut().spawn_empty().id();
+    client_app
+        .world_mut()
+        .resource_mut::<ServerEntityMap>()
+        .insert(server_entity, client_entity);
+
+    client_app
+        .world_mut()
+        .commands()
+        .client_trigger_targets(DummyEvent, [local_entity, client_entity]);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let reader = server_app.world().resource::<TriggerReader>();
+    assert_eq!(
+        reader.events,
+        [(client_id, server_entity)],
+        "unmapped target should be skipped"
+    );
+}
+
+#[test]
+fn local_resending() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, RepliconPlugins))
+        .add_client_trigger::<DummyEvent>(ChannelKind::Ordered)
+        .init_resource::<TriggerReader>()
+        .add_observer(
+            |trigger: Trigger<FromClient<DummyEvent>>, mut reader: ResMut<TriggerReader>| {
+                reader.events.push((trigger.client_id, trigger.entity()));
+            },
+        );
+
+    let entity = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .commands()
+        .client_trigger_targets(DummyEvent, entity);
+
+    app.update();
+    app.update();
+
+    let reader = app.world().resource::<TriggerReader>();
+    assert_eq!(reader.events, [(ClientId::SERVER, entity)]);
+}
+
+#[test]
+fn limit() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .add_client_trigger::<DummyEvent>(ChannelKind::Ordered);
+    }
+    server_app.init_resource::<TriggerReader>();
+    server_app.add_observer(
+        |trigger: Trigger<FromClient<DummyEvent>>, mut reader: ResMut<TriggerReader>| {
+            reader.events.push((trigger.client_id, trigger.entity()));
+        },
+    );
+
+    server_app
+        .world_mut()
+        .resource_mut::<ClientEventLimiter>()
+        .set_limits::<DummyEvent>(ClientEventLimits {
+            max_per_update: Some(1),
+            ..Default::default()
+        });
+
+    server_app.connect_client(&mut client_app);
+
+    let mut commands = client_app.world_mut().commands();
+    commands.client_trigger(DummyEvent);
+    commands.client_trigger(DummyEvent);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+
+    let reader = server_app.world().resource::<TriggerReader>();
+    assert_eq!(reader.events.len(), 1);
+
+    let exceeded: Vec<_> = server_app
+        .world_mut()
+        .resource_mut::<Events<ClientEventLimitExceeded>>()
+        .drain()
+        .collect();
+    let [exceeded] = exceeded.as_slice() else {
+        panic!("one trigger should exceed the limit");
+    };
+    assert_eq!(exceeded.event_name, any::type_name::<DummyEvent>());
+}
+
+#[derive(Event, Deserialize, Serialize)]
+struct DummyEvent;
+
+#[derive(Resource, Default)]
+struct TriggerReader {
+    events: Vec<(ClientId, Entity)>,
+}
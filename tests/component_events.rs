@@ -0,0 +1,278 @@
+use std::io::Cursor;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::replication::{
+        component_events::ReplicationKind,
+        deferred_entity::DeferredEntity,
+        replication_registry::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn insertion_and_mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .add_component_events::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<BoolComponent>>()
+        .single(client_app.world());
+
+    let mut events = client_app
+        .world_mut()
+        .resource_mut::<Events<ComponentReplicated<BoolComponent>>>();
+    let event = events.drain().next().unwrap();
+    assert_eq!(event.entity, client_entity);
+    assert_eq!(event.kind, ReplicationKind::Insertion);
+
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut events = client_app
+        .world_mut()
+        .resource_mut::<Events<ComponentReplicated<BoolComponent>>>();
+    let event = events.drain().next().unwrap();
+    assert_eq!(event.entity, client_entity);
+    assert_eq!(event.kind, ReplicationKind::Mutation);
+
+    // Local writes shouldn't trigger events.
+    client_app
+        .world_mut()
+        .get_mut::<BoolComponent>(client_entity)
+        .unwrap()
+        .0 = false;
+
+    client_app.update();
+
+    let events = client_app
+        .world()
+        .resource::<Events<ComponentReplicated<BoolComponent>>>();
+    assert!(events.is_empty());
+}
+
+#[test]
+fn discarded_write() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .add_component_events::<BoolComponent>()
+        .register_marker::<DiscardMarker>()
+        .set_marker_fns::<DiscardMarker, _>(
+            discard,
+            command_fns::default_remove::<BoolComponent>,
+        );
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<BoolComponent>>()
+        .single(client_app.world());
+
+    client_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert(DiscardMarker);
+    client_app
+        .world_mut()
+        .resource_mut::<Events<ComponentReplicated<BoolComponent>>>()
+        .clear();
+
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world()
+        .get::<BoolComponent>(client_entity)
+        .unwrap();
+    assert!(!component.0, "value should be discarded");
+
+    let events = client_app
+        .world()
+        .resource::<Events<ComponentReplicated<BoolComponent>>>();
+    assert!(events.is_empty());
+}
+
+#[test]
+fn removal() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .add_component_events::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<BoolComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<Replicated>>()
+        .single(client_app.world());
+    assert!(!client_app
+        .world()
+        .entity(client_entity)
+        .contains::<BoolComponent>());
+
+    let mut events = client_app
+        .world_mut()
+        .resource_mut::<Events<ComponentRemovedByServer<BoolComponent>>>();
+    let event = events.drain().next().unwrap();
+    assert_eq!(event.entity, client_entity);
+}
+
+#[test]
+fn removal_of_missing() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>()
+        .add_component_events::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<BoolComponent>>()
+        .single(client_app.world());
+
+    // Remove locally before the server removal arrives.
+    client_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .remove::<BoolComponent>();
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<BoolComponent>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let events = client_app
+        .world()
+        .resource::<Events<ComponentRemovedByServer<BoolComponent>>>();
+    assert!(events.is_empty());
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component)]
+struct DiscardMarker;
+
+/// Deserializes the component without writing it, like prediction that stores values in a history.
+fn discard(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<BoolComponent>,
+    _entity: &mut DeferredEntity,
+    cursor: &mut Cursor<&[u8]>,
+) -> bincode::Result<()> {
+    rule_fns.deserialize(ctx, cursor)?;
+    Ok(())
+}
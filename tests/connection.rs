@@ -1,8 +1,15 @@
-use bevy::prelude::*;
+use bevy::{ecs::entity::MapEntities, prelude::*, time::TimeUpdateStrategy, utils::Duration};
 use bevy_replicon::{
-    core::channels::ReplicationChannel, prelude::*, server::server_tick::ServerTick,
+    core::{
+        channels::ReplicationChannel, message_transform::MessageTransform,
+        server_entity_map::ServerEntityMap,
+    },
+    prelude::*,
+    server::server_tick::ServerTick,
     test_app::ServerTestAppExt,
 };
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
 
 #[test]
 fn client_to_server() {
@@ -245,3 +252,455 @@ fn deferred_replication() {
     let replicated_clients = server_app.world().resource::<ReplicatedClients>();
     assert_eq!(replicated_clients.len(), 1);
 }
+
+#[test]
+fn message_transform() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+        app.world_mut()
+            .resource_mut::<RepliconChannels>()
+            .set_message_transform(XorTransform);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+    assert_eq!(client.decode_failures(), 0);
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    assert_eq!(server.decode_failures(), 0);
+
+    server.insert_received(client_id, ReplicationChannel::Updates, vec![0]);
+    assert_eq!(server.decode_failures(), 1);
+    assert_eq!(server.receive(ReplicationChannel::Updates).count(), 0);
+}
+
+#[test]
+fn message_transform_without_id() {
+    let mut client_app = App::new();
+    client_app.add_plugins((MinimalPlugins, RepliconPlugins));
+    client_app
+        .world_mut()
+        .resource_mut::<RepliconChannels>()
+        .set_message_transform(XorTransform);
+
+    client_app.update();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+
+    client.send(ReplicationChannel::Updates, vec![0]);
+    assert_eq!(
+        client.drain_sent().count(),
+        0,
+        "messages can't be encoded without a client ID"
+    );
+
+    client.insert_received(ReplicationChannel::Updates, vec![0]);
+    assert_eq!(client.decode_failures(), 1);
+    assert_eq!(client.receive(ReplicationChannel::Updates).count(), 0);
+}
+
+#[test]
+fn client_repair() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<BoolComponent>();
+    }
+    client_app.add_plugins(ClientRepairPlugin::default());
+
+    server_app.connect_client(&mut client_app);
+
+    let despawned_entity = server_app.world_mut().spawn(Replicated).id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_entity = *entity_map
+        .to_client()
+        .get(&server_entity)
+        .expect("entity should be replicated");
+
+    server_app.disconnect_client(&mut client_app);
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        2,
+        "entities should be kept on disconnect"
+    );
+
+    server_app.world_mut().despawn(despawned_entity);
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<DummyComponent>()
+        .insert(BoolComponent(true));
+
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(
+        !client_entity.contains::<DummyComponent>(),
+        "component that wasn't re-inserted should be removed"
+    );
+    assert!(client_entity.get::<BoolComponent>().unwrap().0);
+}
+
+#[test]
+fn client_repair_without_updates() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app
+        .add_plugins(ClientRepairPlugin {
+            timeout: Duration::from_secs(2),
+        })
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    server_app.disconnect_client(&mut client_app);
+    server_app.world_mut().despawn(server_entity);
+    server_app.update();
+
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        1,
+        "entity should be kept until the timeout"
+    );
+
+    for _ in 0..2 {
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+    }
+
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        0,
+        "all entities should be despawned after the timeout without updates"
+    );
+}
+
+#[test]
+fn client_repair_mapped() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate_mapped::<MappedComponent>();
+    }
+    client_app.add_plugins(ClientRepairPlugin::default());
+
+    server_app.connect_client(&mut client_app);
+
+    // Spawn the referencing entity first to serialize it before the referenced one.
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, MappedComponent(Entity::PLACEHOLDER)))
+        .id();
+    let server_map_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+    server_app
+        .world_mut()
+        .get_mut::<MappedComponent>(server_entity)
+        .unwrap()
+        .0 = server_map_entity;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    let client_map_entity = *entity_map
+        .to_client()
+        .get(&server_map_entity)
+        .expect("referenced entity should be replicated");
+
+    server_app.disconnect_client(&mut client_app);
+    server_app.connect_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 2);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_map_entity),
+        Some(&client_map_entity),
+        "kept entity should be reused for the mapped reference"
+    );
+
+    let mapped_component = client_app
+        .world_mut()
+        .query::<&MappedComponent>()
+        .single(client_app.world());
+    assert_eq!(mapped_component.0, client_map_entity);
+    assert!(client_app
+        .world()
+        .entity(client_map_entity)
+        .contains::<DummyComponent>());
+}
+
+#[test]
+fn server_disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_event::<DummyEvent>(ChannelKind::Ordered);
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    server_app.world_mut().send_event(ToClients {
+        mode: SendMode::Direct(client_id),
+        event: DummyEvent,
+    });
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .disconnect(client_id, "Kicked");
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let events = client_app.world().resource::<Events<DummyEvent>>();
+    assert_eq!(
+        events.len(),
+        1,
+        "final event should be sent before disconnect"
+    );
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    let disconnects: Vec<_> = server.drain_disconnects().collect();
+    assert_eq!(disconnects, [(client_id, "Kicked".to_string())]);
+
+    for (client_id, reason) in disconnects {
+        server_app
+            .world_mut()
+            .trigger(ServerEvent::ClientDisconnected { client_id, reason });
+    }
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    assert!(replicated_clients.is_empty());
+}
+
+#[test]
+fn server_disconnect_reason() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    server_app
+        .world_mut()
+        .resource_mut::<RepliconServer>()
+        .disconnect(client_id, "Kicked");
+
+    server_app.disconnect_client_by_server(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    assert!(client.is_disconnected());
+    assert_eq!(
+        client.disconnect_reason(),
+        Some(&DisconnectReason::Server("Kicked".to_string()))
+    );
+
+    let replicated_clients = server_app.world().resource::<ReplicatedClients>();
+    assert!(replicated_clients.is_empty());
+}
+
+#[test]
+fn disconnect_reason() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app
+        .init_resource::<DisconnectCounters>()
+        .add_systems(
+            Update,
+            (
+                (|mut counters: ResMut<DisconnectCounters>| counters.server += 1).run_if(
+                    client_just_disconnected_with(|reason| {
+                        matches!(reason, DisconnectReason::Server(_))
+                    }),
+                ),
+                (|mut counters: ResMut<DisconnectCounters>| counters.timed_out += 1).run_if(
+                    client_just_disconnected_with(|reason| *reason == DisconnectReason::TimedOut),
+                ),
+            ),
+        );
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    assert_eq!(client.disconnect_reason(), None);
+
+    server_app.disconnect_client_by_server(&mut client_app);
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Disconnected);
+    assert_eq!(
+        client.disconnect_reason(),
+        Some(&DisconnectReason::Server(
+            "Disconnected by server".to_string()
+        )),
+        "reason should be kept while disconnected"
+    );
+
+    client_app.update();
+
+    let counters = client_app.world().resource::<DisconnectCounters>();
+    assert_eq!(counters.server, 1, "condition should trigger only once");
+    assert_eq!(counters.timed_out, 0);
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    assert_eq!(
+        client.disconnect_reason(),
+        None,
+        "reason should be cleared on status change"
+    );
+}
+
+#[derive(Resource, Default)]
+struct DisconnectCounters {
+    server: usize,
+    timed_out: usize,
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Deserialize, Event, Serialize)]
+struct DummyEvent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct MappedComponent(Entity);
+
+impl MapEntities for MappedComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+/// XORs all bytes with the client ID and appends the key to validate on decode.
+struct XorTransform;
+
+impl MessageTransform for XorTransform {
+    fn encode(&self, client_id: ClientId, _channel_id: u8, message: Bytes) -> Bytes {
+        let key = client_id.get() as u8;
+        message.iter().map(|byte| byte ^ key).chain([key]).collect()
+    }
+
+    fn decode(&self, client_id: ClientId, _channel_id: u8, message: Bytes) -> Option<Bytes> {
+        let key = client_id.get() as u8;
+        let (&last, data) = message.split_last()?;
+        (last == key).then(|| data.iter().map(|byte| byte ^ key).collect())
+    }
+}
@@ -0,0 +1,296 @@
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use bevy_replicon::{
+    client::interpolation::{InterpolationBuffer, InterpolationTime},
+    core::channels::ReplicationChannel,
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn interpolation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Position>();
+    }
+    setup_interpolation(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    let (server_entity, client_entity) = spawn_interpolated(&mut server_app, &mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<Position>(server_entity)
+        .unwrap()
+        .0 = 10.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let position = client_app.world().get::<Position>(client_entity).unwrap();
+    assert_eq!(position.0, 0.0, "should stay behind the latest tick");
+
+    client_app.update();
+
+    let position = client_app.world().get::<Position>(client_entity).unwrap();
+    assert_eq!(position.0, 5.0, "should be halfway between snapshots");
+
+    client_app.update();
+
+    let position = client_app.world().get::<Position>(client_entity).unwrap();
+    assert_eq!(position.0, 10.0);
+}
+
+#[test]
+fn late_mutation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Position>();
+    }
+    setup_interpolation(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    let (server_entity, client_entity) = spawn_interpolated(&mut server_app, &mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<Position>(server_entity)
+        .unwrap()
+        .0 = 10.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Take the mutation to deliver it after the next one.
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    let messages: Vec<_> = client.receive(ReplicationChannel::Mutations).collect();
+    assert_eq!(messages.len(), 1);
+
+    server_app
+        .world_mut()
+        .get_mut::<Position>(server_entity)
+        .unwrap()
+        .0 = 20.0;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    for message in messages {
+        client.insert_received(ReplicationChannel::Mutations, message);
+    }
+    client_app.update();
+
+    let buffer = client_app
+        .world()
+        .get::<InterpolationBuffer<Position>>(client_entity)
+        .unwrap();
+    let values: Vec<_> = buffer.iter().map(|(_, position)| position.0).collect();
+    assert_eq!(values, [0.0, 10.0, 20.0], "late value should fill the gap");
+
+    let position = client_app.world().get::<Position>(client_entity).unwrap();
+    assert_eq!(position.0, 5.0);
+}
+
+#[test]
+fn other_entities() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Position>();
+    }
+    setup_interpolation(&mut client_app);
+    client_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+
+    server_app.connect_client(&mut client_app);
+
+    spawn_interpolated(&mut server_app, &mut client_app);
+    let other_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Position(0.0)))
+        .id();
+
+    let mut last_tick = client_app.world().resource::<InterpolationTime>().tick();
+    for _ in 0..10 {
+        server_app
+            .world_mut()
+            .get_mut::<Position>(other_entity)
+            .unwrap()
+            .0 += 1.0;
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let tick = client_app.world().resource::<InterpolationTime>().tick();
+        assert_eq!(
+            tick,
+            last_tick + 1,
+            "should follow ticks of non-interpolated entities without snapping"
+        );
+        last_tick = tick;
+    }
+}
+
+#[test]
+fn zero_delay() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Position>();
+    }
+    client_app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            50,
+        )))
+        .add_plugins(InterpolationPlugin {
+            delay: 0,
+            tick_duration: Duration::from_millis(100),
+        })
+        .interpolate::<Position>(|from, to, fraction| {
+            Position(from.0 + (to.0 - from.0) * fraction)
+        });
+
+    server_app.connect_client(&mut client_app);
+
+    spawn_interpolated(&mut server_app, &mut client_app);
+    let received_tick = client_app.world().resource::<InterpolationTime>().tick();
+
+    client_app.update();
+    client_app.update();
+
+    let interpolation_time = client_app.world().resource::<InterpolationTime>();
+    assert_eq!(
+        interpolation_time.tick(),
+        received_tick + 1,
+        "small drift shouldn't cause snapping"
+    );
+}
+
+#[test]
+fn server_clock_tick_duration() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Position>();
+    }
+    client_app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            50,
+        )))
+        .add_plugins((
+            ServerClockPlugin {
+                tick_duration: Duration::from_millis(100),
+                ..Default::default()
+            },
+            InterpolationPlugin {
+                delay: 0,
+                tick_duration: Duration::from_secs(1),
+            },
+        ))
+        .interpolate::<Position>(|from, to, fraction| {
+            Position(from.0 + (to.0 - from.0) * fraction)
+        });
+
+    server_app.connect_client(&mut client_app);
+
+    spawn_interpolated(&mut server_app, &mut client_app);
+    let received_tick = client_app.world().resource::<InterpolationTime>().tick();
+
+    client_app.update();
+    client_app.update();
+
+    let interpolation_time = client_app.world().resource::<InterpolationTime>();
+    assert_eq!(
+        interpolation_time.tick(),
+        received_tick + 1,
+        "tick duration should be taken from the server clock"
+    );
+}
+
+fn setup_interpolation(app: &mut App) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        50,
+    )))
+    .add_plugins(InterpolationPlugin {
+        delay: 1,
+        tick_duration: Duration::from_millis(100),
+    })
+    .interpolate::<Position>(|from, to, fraction| Position(from.0 + (to.0 - from.0) * fraction));
+}
+
+/// Spawns entity with [`Position`] on server and an interpolated entity on client.
+fn spawn_interpolated(server_app: &mut App, client_app: &mut App) -> (Entity, Entity) {
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, Position(0.0)))
+        .id();
+    let client_entity = client_app.world_mut().spawn(Interpolated).id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(client_app);
+    client_app.update();
+    server_app.exchange_with_client(client_app);
+
+    (server_entity, client_entity)
+}
+
+#[derive(Component, Deserialize, Serialize, Clone, Copy, PartialEq)]
+struct Position(f32);
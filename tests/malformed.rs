@@ -0,0 +1,436 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::{
+    client::ServerUpdateTick,
+    core::{
+        channels::ReplicationChannel,
+        entity_serde,
+        replication::replication_registry::{rule_fns::RuleFns, ReplicationRegistry},
+        replicon_tick::RepliconTick,
+        server_entity_map::ServerEntityMap,
+    },
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+/// Update message with changes for tick 1, but truncated entity.
+const MALFORMED_UPDATE: &[u8] = &[CHANGES_FLAGS, 1, 0xFF];
+
+/// Bits of `UpdateMessageFlags::CHANGES` and `UpdateMessageFlags::VARINT_TICK`.
+const CHANGES_FLAGS: u8 = 0b10010000;
+
+/// Bits of `UpdateMessageFlags::MAPPINGS`.
+const MAPPINGS_FLAG: u8 = 0b00000001;
+
+#[test]
+fn skip() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, MALFORMED_UPDATE);
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut errors = client_app
+        .world_mut()
+        .resource_mut::<Events<ReplicationError>>();
+    let error = errors.drain().next().unwrap();
+    assert_eq!(error.channel, ReplicationChannel::Updates);
+    assert_eq!(error.tick, Some(RepliconTick::new(1)));
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(
+        replicated.iter(client_app.world()).len(),
+        1,
+        "valid message should be applied"
+    );
+}
+
+#[test]
+fn malformed_header() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Mutations, Vec::new());
+
+    client_app.update();
+
+    let mut errors = client_app
+        .world_mut()
+        .resource_mut::<Events<ReplicationError>>();
+    let error = errors.drain().next().unwrap();
+    assert_eq!(error.channel, ReplicationChannel::Mutations);
+    assert_eq!(error.tick, None);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    assert!(client.is_connected());
+}
+
+#[test]
+fn reset() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app.insert_resource(MalformedMessagePolicy::Reset);
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(entity_map.to_client().len(), 1);
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, MALFORMED_UPDATE);
+
+    client_app.update();
+
+    let resync_requests = client_app.world().resource::<Events<ResyncRequest>>();
+    assert_eq!(resync_requests.len(), 1);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(
+        replicated.iter(client_app.world()).len(),
+        1,
+        "resync should replace the existing entity instead of spawning a duplicate"
+    );
+}
+
+#[test]
+fn disconnect() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app.insert_resource(MalformedMessagePolicy::Disconnect);
+
+    server_app.connect_client(&mut client_app);
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, MALFORMED_UPDATE);
+
+    client_app.update();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    assert!(client.is_disconnected());
+    assert_eq!(
+        client.disconnect_reason(),
+        Some(&DisconnectReason::MalformedMessage)
+    );
+    assert!(client.take_disconnect_request());
+    assert!(!client.take_disconnect_request());
+
+    let errors = client_app.world().resource::<Events<ReplicationError>>();
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn unknown_fns_id() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Changes for tick 1 with a single component for a new entity, but the function ID is unknown.
+    let message = [CHANGES_FLAGS, 1, 20, 1, 100];
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, message.to_vec());
+
+    client_app.update();
+
+    assert_not_applied(&mut client_app);
+}
+
+#[test]
+fn malformed_entity() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn(PendingMapping::default()).id();
+
+    // Mapping for the pre-spawned entity and changes for tick 1,
+    // but the entity after the mapped one is truncated.
+    let mut message = vec![MAPPINGS_FLAG | CHANGES_FLAGS, 1, 1, 20];
+    entity_serde::serialize_entity(&mut message, client_entity).unwrap();
+    message.extend([20, 0, 0xFF]);
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, message);
+
+    client_app.update();
+
+    assert_not_applied(&mut client_app);
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(client_entity.contains::<PendingMapping>());
+
+    let outcomes = client_app.world().resource::<Events<MappingOutcome>>();
+    assert!(outcomes.is_empty());
+}
+
+#[test]
+fn truncated_component() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let (_, fns_id) =
+        client_app
+            .world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(world, RuleFns::<TestComponent>::default())
+            });
+
+    // Changes for tick 1 with a single component for a new entity,
+    // but the component data is cut after the varint marker.
+    let mut message = vec![CHANGES_FLAGS, 1, 20, 1];
+    bincode::DefaultOptions::new()
+        .serialize_into(&mut message, &fns_id)
+        .unwrap();
+    message.push(251);
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, message);
+
+    client_app.update();
+
+    assert_not_applied(&mut client_app);
+}
+
+#[test]
+fn mapped_entity_reverted() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let (_, fns_id) =
+        client_app
+            .world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(world, RuleFns::<MappedComponent>::default_mapped())
+            });
+
+    // Changes for tick 1 with a component that references an unknown entity,
+    // but the entity after it is truncated.
+    let mut message = vec![CHANGES_FLAGS, 1, 20, 1];
+    bincode::DefaultOptions::new()
+        .serialize_into(&mut message, &fns_id)
+        .unwrap();
+    bincode::DefaultOptions::new()
+        .serialize_into(&mut message, &MappedComponent(Entity::from_raw(30)))
+        .unwrap();
+    message.push(0xFF);
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, message);
+
+    client_app.update();
+
+    assert_not_applied(&mut client_app);
+}
+
+#[test]
+fn existing_entity_kept() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let (_, fns_id) =
+        client_app
+            .world_mut()
+            .resource_scope(|world, mut registry: Mut<ReplicationRegistry>| {
+                registry.register_rule_fns(world, RuleFns::<BoolComponent>::default())
+            });
+
+    let server_entity = server_app.world_mut().spawn_empty().id();
+    let client_entity = client_app
+        .world_mut()
+        .spawn((Replicated, TestComponent(1)))
+        .id();
+    client_app
+        .world_mut()
+        .resource_mut::<ServerEntityMap>()
+        .insert(server_entity, client_entity);
+
+    // Changes for tick 1 with a new entity without components, followed by the existing entity
+    // with an inserted component and a component with unknown function ID.
+    let mut message = vec![CHANGES_FLAGS, 1, 20, 0];
+    entity_serde::serialize_entity(&mut message, server_entity).unwrap();
+    message.push(2);
+    bincode::DefaultOptions::new()
+        .serialize_into(&mut message, &fns_id)
+        .unwrap();
+    bincode::DefaultOptions::new()
+        .serialize_into(&mut message, &BoolComponent(true))
+        .unwrap();
+    message.push(100);
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.insert_received(ReplicationChannel::Updates, message);
+
+    client_app.update();
+
+    let errors = client_app.world().resource::<Events<ReplicationError>>();
+    assert_eq!(errors.len(), 1);
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(
+        replicated.iter(client_app.world()).len(),
+        1,
+        "only the entity spawned by the malformed message should be reverted"
+    );
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(entity_map.to_client().len(), 1);
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity),
+        "existing entity should stay mapped"
+    );
+    let client_entity = client_app.world().entity(client_entity);
+    assert_eq!(
+        client_entity.get::<TestComponent>(),
+        Some(&TestComponent(1))
+    );
+    assert!(
+        !client_entity.contains::<BoolComponent>(),
+        "partially applied changes should be rolled back"
+    );
+    assert_eq!(
+        **client_app.world().resource::<ServerUpdateTick>(),
+        RepliconTick::default()
+    );
+}
+
+/// Asserts that the client reported an error and didn't apply anything from the message.
+fn assert_not_applied(client_app: &mut App) {
+    let errors = client_app.world().resource::<Events<ReplicationError>>();
+    assert_eq!(errors.len(), 1);
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).len(), 0);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+    assert_eq!(
+        **client_app.world().resource::<ServerUpdateTick>(),
+        RepliconTick::default()
+    );
+}
+
+#[derive(Component, Debug, Deserialize, PartialEq, Serialize)]
+struct TestComponent(u32);
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+#[derive(Component, Deserialize, Serialize)]
+struct MappedComponent(Entity);
+
+impl MapEntities for MappedComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
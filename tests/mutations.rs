@@ -65,6 +65,62 @@ fn small_component() {
     assert!(component.0, "mutated value should be updated on client");
 }
 
+#[test]
+fn large_tick() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    // Large enough to be serialized as fixint.
+    server_app
+        .world_mut()
+        .resource_mut::<ServerTick>()
+        .increment_by(u32::MAX / 2);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    assert_eq!(
+        **client_app.world().resource::<ServerUpdateTick>(),
+        server_tick
+    );
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(component.0, "mutated value should be updated on client");
+}
+
 #[test]
 fn package_size_component() {
     let mut server_app = App::new();
@@ -539,6 +595,92 @@ fn marker_with_history_old_update() {
     );
 }
 
+#[test]
+fn marker_with_long_history() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    history_len: 128,
+                    ..Default::default()
+                }),
+        ))
+        .register_marker_with::<HistoryMarker>(MarkerConfig {
+            need_history: true,
+            ..Default::default()
+        })
+        .set_marker_fns::<HistoryMarker, BoolComponent>(
+            write_history,
+            command_fns::default_remove::<BoolComponent>,
+        )
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    let client_entity = client_app.world_mut().spawn(HistoryMarker).id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Make the last confirmed tick larger than the default history length.
+    let mut tick = **server_app.world().resource::<ServerTick>();
+    tick += u64::BITS + 1;
+    let mut history = client_app
+        .world_mut()
+        .get_mut::<ConfirmHistory>(client_entity)
+        .unwrap();
+    assert_eq!(history.history_len(), 128);
+    history.confirm(tick);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let history = client_app
+        .world()
+        .get::<BoolHistory>(client_entity)
+        .unwrap();
+
+    assert_eq!(
+        history.0,
+        [false, true],
+        "mutation should fit into the extended history"
+    );
+}
+
 #[test]
 fn many_entities() {
     let mut server_app = App::new();
@@ -591,6 +733,73 @@ fn many_entities() {
     }
 }
 
+#[test]
+fn multiple_clients() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    let mut client_app3 = App::new();
+    for app in [
+        &mut server_app,
+        &mut client_app1,
+        &mut client_app2,
+        &mut client_app3,
+    ] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                clients_per_task: 1, // Split collection to cover merging of serialized data.
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+
+    for client_app in [&mut client_app1, &mut client_app2, &mut client_app3] {
+        server_app.connect_client(client_app);
+    }
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+    let despawned_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2, &mut client_app3] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+        server_app.exchange_with_client(client_app);
+
+        let mut replicated = client_app.world_mut().query::<&Replicated>();
+        assert_eq!(replicated.iter(client_app.world()).count(), 2);
+    }
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+    // Despawns are serialized before changes, so merged ranges won't point to the beginning of the data.
+    server_app.world_mut().despawn(despawned_entity);
+
+    server_app.update();
+    for client_app in [&mut client_app1, &mut client_app2, &mut client_app3] {
+        server_app.exchange_with_client(client_app);
+        client_app.update();
+
+        let mut replicated = client_app.world_mut().query::<&Replicated>();
+        assert_eq!(replicated.iter(client_app.world()).count(), 1);
+
+        let component = client_app
+            .world_mut()
+            .query::<&BoolComponent>()
+            .single(client_app.world());
+        assert!(component.0);
+    }
+}
+
 #[test]
 fn with_insertion() {
     let mut server_app = App::new();
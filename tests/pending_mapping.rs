@@ -0,0 +1,238 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn mapped() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn(PendingMapping::default()).id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity)
+    );
+
+    let client_entity_ref = client_app.world().entity(client_entity);
+    assert!(!client_entity_ref.contains::<PendingMapping>());
+    assert!(client_entity_ref.contains::<DummyComponent>());
+
+    let outcomes = drain_outcomes(&mut client_app);
+    assert_eq!(
+        outcomes,
+        [MappingOutcome::Mapped {
+            client_entity,
+            server_entity
+        }]
+    );
+}
+
+#[test]
+fn rejected() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn(PendingMapping::default()).id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.reject(client_id, client_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app.world().get_entity(client_entity).is_err(),
+        "rejected entity should be despawned"
+    );
+
+    let outcomes = drain_outcomes(&mut client_app);
+    assert_eq!(outcomes, [MappingOutcome::Rejected { client_entity }]);
+}
+
+#[test]
+fn rejected_without_pending() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn_empty().id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.reject(client_id, client_entity);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(
+        client_app.world().get_entity(client_entity).is_ok(),
+        "entity without pending mapping should be kept"
+    );
+
+    let outcomes = drain_outcomes(&mut client_app);
+    assert_eq!(outcomes, [MappingOutcome::Rejected { client_entity }]);
+}
+
+#[test]
+fn dropped() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    client_app.insert_resource(UnmappedEntityPolicy::Keep);
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn(PendingMapping::default()).id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity_ref = client_app.world().entity(client_entity);
+    assert!(
+        !client_entity_ref.contains::<PendingMapping>(),
+        "entity should be kept according to the policy"
+    );
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+
+    let outcomes = drain_outcomes(&mut client_app);
+    assert_eq!(outcomes, [MappingOutcome::Dropped { client_entity }]);
+}
+
+#[test]
+fn expired() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins
+                .set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                })
+                .set(ClientPlugin {
+                    mapping_timeout: Duration::ZERO,
+                    ..Default::default()
+                }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn(PendingMapping::default()).id();
+
+    client_app.update();
+
+    assert!(
+        client_app.world().get_entity(client_entity).is_err(),
+        "expired entity should be despawned"
+    );
+
+    let outcomes = drain_outcomes(&mut client_app);
+    assert_eq!(outcomes, [MappingOutcome::Expired { client_entity }]);
+}
+
+fn drain_outcomes(app: &mut App) -> Vec<MappingOutcome> {
+    app.world_mut()
+        .resource_mut::<Events<MappingOutcome>>()
+        .drain()
+        .collect()
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
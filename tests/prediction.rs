@@ -0,0 +1,466 @@
+use std::time::Duration;
+
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+use bevy_replicon::{
+    client::{
+        prediction::{PredictionHistory, PredictionTick},
+        server_clock::{ClientTick, ServerClockPlugin},
+    },
+    core::replicon_tick::RepliconTick,
+    prelude::*,
+    server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn confirmed() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Counter>();
+    }
+    setup_prediction(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    let (server_entity, client_entity) = spawn_predicted(&mut server_app, &mut client_app);
+    let first_tick = tick(&client_app);
+    assert_eq!(
+        first_tick,
+        **server_app.world().resource::<ServerTick>(),
+        "client tick should be synced with the server"
+    );
+
+    simulate(&mut client_app);
+    simulate(&mut client_app);
+    assert_eq!(tick(&client_app), first_tick + 2);
+
+    // Send the same value as predicted by the client.
+    server_app
+        .world_mut()
+        .get_mut::<Counter>(server_entity)
+        .unwrap()
+        .0 = 1;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(tick(&client_app), first_tick + 2, "shouldn't roll back");
+    let client_entity = client_app.world().entity(client_entity);
+    assert_eq!(client_entity.get::<Counter>().unwrap().0, 2);
+    let history = client_entity.get::<PredictionHistory<Counter>>().unwrap();
+    assert_eq!(
+        history.front().map(|&(tick, _)| tick),
+        Some(first_tick + 1),
+        "confirmed values should be removed"
+    );
+}
+
+#[test]
+fn mismatch() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Counter>();
+    }
+    setup_prediction(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    let (server_entity, client_entity) = spawn_predicted(&mut server_app, &mut client_app);
+    let first_tick = tick(&client_app);
+
+    for _ in 0..3 {
+        simulate(&mut client_app);
+    }
+    assert_eq!(tick(&client_app), first_tick + 3);
+
+    // Skip tick without changes.
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Send a value that differs from the predicted.
+    server_app
+        .world_mut()
+        .get_mut::<Counter>(server_entity)
+        .unwrap()
+        .0 = 10;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(tick(&client_app), first_tick + 3);
+    let counter = client_app.world().get::<Counter>(client_entity).unwrap();
+    assert_eq!(
+        counter.0, 11,
+        "should re-simulate the tick after the mismatched"
+    );
+}
+
+#[test]
+fn first_value() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Counter>();
+    }
+    setup_prediction(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    let (_, client_entity) = spawn_predicted(&mut server_app, &mut client_app);
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert_eq!(
+        client_entity.get::<Counter>().unwrap().0,
+        0,
+        "first value should be inserted as is"
+    );
+    let history = client_entity.get::<PredictionHistory<Counter>>().unwrap();
+    assert_eq!(history.len(), 1);
+}
+
+#[test]
+fn fixed_time() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Counter>();
+    }
+    setup_prediction(&mut client_app);
+    client_app
+        .init_resource::<SimulationDeltas>()
+        .add_systems(Simulation, record_delta.in_set(PredictionSet::Simulate));
+
+    server_app.connect_client(&mut client_app);
+
+    let (server_entity, _) = spawn_predicted(&mut server_app, &mut client_app);
+
+    for _ in 0..3 {
+        simulate(&mut client_app);
+    }
+    client_app
+        .world_mut()
+        .resource_mut::<SimulationDeltas>()
+        .0
+        .clear();
+
+    server_app
+        .world_mut()
+        .get_mut::<Counter>(server_entity)
+        .unwrap()
+        .0 = 10;
+
+    const FIXED_DELTA: Duration = Duration::from_millis(100);
+    client_app
+        .world_mut()
+        .resource_mut::<Time<Fixed>>()
+        .advance_by(FIXED_DELTA);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let deltas = &client_app.world().resource::<SimulationDeltas>().0;
+    assert!(!deltas.is_empty(), "should re-simulate");
+    assert!(
+        deltas.iter().all(|&delta| delta == FIXED_DELTA),
+        "re-simulation should use fixed time"
+    );
+}
+
+#[test]
+fn outdated_mismatch() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Counter>();
+    }
+    setup_prediction(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    let (server_entity, client_entity) = spawn_predicted(&mut server_app, &mut client_app);
+    let first_tick = tick(&client_app);
+
+    for _ in 0..HISTORY_LEN + 2 {
+        simulate(&mut client_app);
+    }
+
+    server_app
+        .world_mut()
+        .get_mut::<Counter>(server_entity)
+        .unwrap()
+        .0 = 10;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(tick(&client_app), first_tick + HISTORY_LEN as u32 + 2);
+    let counter = client_app.world().get::<Counter>(client_entity).unwrap();
+    assert_eq!(
+        counter.0,
+        HISTORY_LEN as u32 + 2,
+        "shouldn't roll back to a tick older than the history"
+    );
+}
+
+#[test]
+fn removal() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Counter>();
+    }
+    setup_prediction(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    let (server_entity, client_entity) = spawn_predicted(&mut server_app, &mut client_app);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .remove::<Counter>();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(!client_entity.contains::<Counter>());
+    assert!(!client_entity.contains::<PredictionHistory<Counter>>());
+}
+
+#[test]
+fn unrecorded_at_rollback() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Counter>();
+    }
+    setup_prediction(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    let (server_entity, client_entity) = spawn_predicted(&mut server_app, &mut client_app);
+    let first_tick = tick(&client_app);
+
+    for _ in 0..3 {
+        simulate(&mut client_app);
+    }
+
+    // Predicted entity without any recorded values, like a pre-spawned entity that wasn't confirmed yet.
+    let unrecorded_entity = client_app.world_mut().spawn((Predicted, Counter(0))).id();
+
+    // Skip tick without changes.
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<Counter>(server_entity)
+        .unwrap()
+        .0 = 10;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(tick(&client_app), first_tick + 3);
+    let counter = client_app.world().get::<Counter>(client_entity).unwrap();
+    assert_eq!(counter.0, 11);
+
+    let unrecorded_entity = client_app.world().entity(unrecorded_entity);
+    assert_eq!(
+        unrecorded_entity.get::<Counter>().unwrap().0,
+        0,
+        "entity without a value at the rollback tick shouldn't be re-simulated"
+    );
+    assert!(unrecorded_entity.contains::<Predicted>());
+}
+
+#[test]
+fn snap_on_skipped_rollback() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<Counter>();
+    }
+    setup_prediction(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    let (server_entity, client_entity) = spawn_predicted(&mut server_app, &mut client_app);
+
+    for _ in 0..2 {
+        simulate(&mut client_app);
+    }
+
+    // Advance the tick without recording values to make the rollback too long.
+    for _ in 0..HISTORY_LEN {
+        client_app.world_mut().run_schedule(FixedFirst);
+    }
+
+    server_app
+        .world_mut()
+        .get_mut::<Counter>(server_entity)
+        .unwrap()
+        .0 = 10;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let counter = client_app.world().get::<Counter>(client_entity).unwrap();
+    assert_eq!(
+        counter.0, 10,
+        "mismatched value should be set to the confirmed one without rollback"
+    );
+}
+
+fn setup_prediction(app: &mut App) {
+    app.add_plugins((
+        ServerClockPlugin {
+            // Large duration to prevent the clock from advancing on its own.
+            tick_duration: Duration::from_secs(3600),
+            fixed_lead: Some(0),
+            ..Default::default()
+        },
+        PredictionPlugin {
+            schedule: Simulation.intern(),
+            history_len: HISTORY_LEN,
+        },
+    ))
+    .predict::<Counter>()
+    .add_systems(Simulation, increment.in_set(PredictionSet::Simulate));
+}
+
+/// Spawns entity with [`Counter`] on server and a predicted entity on client.
+fn spawn_predicted(server_app: &mut App, client_app: &mut App) -> (Entity, Entity) {
+    let server_entity = server_app.world_mut().spawn((Replicated, Counter(0))).id();
+    let client_entity = client_app.world_mut().spawn(Predicted).id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(client_app);
+    client_app.update();
+    server_app.exchange_with_client(client_app);
+
+    (server_entity, client_entity)
+}
+
+/// Advances [`ClientTick`] and simulates it.
+fn simulate(app: &mut App) {
+    app.world_mut().run_schedule(FixedFirst);
+    app.world_mut().run_schedule(Simulation);
+    assert_eq!(
+        **app.world().resource::<PredictionTick>(),
+        **app.world().resource::<ClientTick>()
+    );
+}
+
+fn tick(app: &App) -> RepliconTick {
+    **app.world().resource::<ClientTick>()
+}
+
+fn increment(
+    skipped: Res<RollbackSkipped>,
+    mut counters: Query<(Entity, &mut Counter), With<Predicted>>,
+) {
+    for (entity, mut counter) in &mut counters {
+        if !skipped.contains(&entity) {
+            counter.0 += 1;
+        }
+    }
+}
+
+fn record_delta(time: Res<Time>, mut deltas: ResMut<SimulationDeltas>) {
+    deltas.0.push(time.delta());
+}
+
+const HISTORY_LEN: usize = 4;
+
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+struct Simulation;
+
+#[derive(Resource, Default)]
+struct SimulationDeltas(Vec<Duration>);
+
+#[derive(Component, Deserialize, Serialize, Clone, PartialEq)]
+struct Counter(u32);
@@ -0,0 +1,193 @@
+use std::{fs, io};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use bevy_replicon::{
+    core::{message_transform::MessageTransform, ClientId},
+    prelude::*,
+    test_app::ServerTestAppExt,
+};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn record_and_play() {
+    let path = std::env::temp_dir().join("bevy_replicon_record_and_play.bin");
+
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ReplayPlugin,
+        ))
+        .replicate::<BoolComponent>();
+    }
+    setup_time(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.insert_resource(ReplayRecorder::create(&path).unwrap());
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let mut component = server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap();
+    component.0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app.world_mut().remove_resource::<ReplayRecorder>();
+
+    let mut replay_app = App::new();
+    replay_app
+        .add_plugins((MinimalPlugins, RepliconPlugins, ReplayPlugin))
+        .replicate::<BoolComponent>()
+        .insert_resource(ReplayPlayer::open(&path).unwrap());
+    setup_time(&mut replay_app);
+
+    replay_app.update();
+
+    let component = replay_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(replay_app.world());
+    assert!(!component.0, "only the first frame should be replayed");
+
+    replay_app.update();
+
+    let component = replay_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(replay_app.world());
+    assert!(component.0);
+
+    replay_app.update();
+
+    let player = replay_app.world().resource::<ReplayPlayer>();
+    assert!(player.is_finished());
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn play_with_transform() {
+    let path = std::env::temp_dir().join("bevy_replicon_play_with_transform.bin");
+
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+            ReplayPlugin,
+        ))
+        .replicate::<BoolComponent>();
+        app.world_mut()
+            .resource_mut::<RepliconChannels>()
+            .set_message_transform(XorTransform);
+    }
+    setup_time(&mut client_app);
+
+    server_app.connect_client(&mut client_app);
+
+    client_app.insert_resource(ReplayRecorder::create(&path).unwrap());
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    client_app.world_mut().remove_resource::<ReplayRecorder>();
+
+    let mut replay_app = App::new();
+    replay_app
+        .add_plugins((MinimalPlugins, RepliconPlugins, ReplayPlugin))
+        .replicate::<BoolComponent>()
+        .insert_resource(ReplayPlayer::open(&path).unwrap());
+    replay_app
+        .world_mut()
+        .resource_mut::<RepliconChannels>()
+        .set_message_transform(XorTransform);
+    setup_time(&mut replay_app);
+
+    replay_app.update();
+
+    let mut components = replay_app.world_mut().query::<&BoolComponent>();
+    assert_eq!(components.iter(replay_app.world()).count(), 1);
+
+    let client = replay_app.world().resource::<RepliconClient>();
+    assert_eq!(client.decode_failures(), 0);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn oversized_record() {
+    // Timestamp, channel ID and a message length that exceeds the limit.
+    let mut data = b"RPL1".to_vec();
+    data.extend([0, 0, 0, 253]);
+    data.extend(u64::MAX.to_le_bytes());
+
+    let mut replay_app = App::new();
+    replay_app
+        .add_plugins((MinimalPlugins, RepliconPlugins, ReplayPlugin))
+        .insert_resource(ReplayPlayer::new(io::Cursor::new(data)).unwrap());
+
+    replay_app.update();
+
+    let player = replay_app.world().resource::<ReplayPlayer>();
+    assert!(player.is_finished());
+}
+
+#[test]
+fn invalid_data() {
+    let data: &[u8] = b"not a recording";
+    assert!(ReplayPlayer::new(data).is_err());
+}
+
+fn setup_time(app: &mut App) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+/// XORs all bytes with the client ID and appends the key to validate on decode.
+struct XorTransform;
+
+impl MessageTransform for XorTransform {
+    fn encode(&self, client_id: ClientId, _channel_id: u8, message: Bytes) -> Bytes {
+        let key = client_id.get() as u8;
+        message.iter().map(|byte| byte ^ key).chain([key]).collect()
+    }
+
+    fn decode(&self, client_id: ClientId, _channel_id: u8, message: Bytes) -> Option<Bytes> {
+        let key = client_id.get() as u8;
+        let (&last, data) = message.split_last()?;
+        (last == key).then(|| data.iter().map(|byte| byte ^ key).collect())
+    }
+}
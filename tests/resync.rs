@@ -0,0 +1,278 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap, prelude::*, server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn components() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>()
+        .replicate::<BoolComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(true)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Simulate a drift.
+    let client_entity = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<BoolComponent>>()
+        .single(client_app.world());
+    client_app
+        .world_mut()
+        .entity_mut(client_entity)
+        .insert((DummyComponent, BoolComponent(false)));
+
+    client_app.world_mut().send_event(ResyncRequest);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client_entity = client_app.world().entity(client_entity);
+    assert!(
+        !client_entity.contains::<DummyComponent>(),
+        "component missing on server should be removed"
+    );
+    let component = client_entity.get::<BoolComponent>().unwrap();
+    assert!(component.0, "component should be overwritten");
+}
+
+#[test]
+fn entities() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Simulate a missed despawn.
+    let stale_entity = client_app.world_mut().spawn(Replicated).id();
+    let mut entity_map = client_app.world_mut().resource_mut::<ServerEntityMap>();
+    entity_map.insert(Entity::from_raw(u32::MAX), stale_entity);
+
+    client_app.world_mut().send_event(ResyncRequest);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world().get_entity(stale_entity).is_err());
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(entity_map.to_client().len(), 1);
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+}
+
+#[test]
+fn empty() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let stale_entity = client_app.world_mut().spawn(Replicated).id();
+    let mut entity_map = client_app.world_mut().resource_mut::<ServerEntityMap>();
+    entity_map.insert(Entity::from_raw(u32::MAX), stale_entity);
+
+    client_app.world_mut().send_event(ResyncRequest);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert!(client_app.world().get_entity(stale_entity).is_err());
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().is_empty());
+}
+
+#[test]
+fn visibility_change() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app.init_resource::<ClientReplicationStats>();
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let hidden_entity = server_app.world_mut().spawn(Replicated).id();
+    let shown_entity = server_app.world_mut().spawn(Replicated).id();
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(hidden_entity, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    client_app.world_mut().send_event(ResyncRequest);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Change visibility before the server receives the request.
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    let visibility = replicated_clients.client_mut(client_id).visibility_mut();
+    visibility.set_visibility(hidden_entity, false);
+    visibility.set_visibility(shown_entity, true);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(!entity_map.to_client().contains_key(&hidden_entity));
+    assert!(entity_map.to_client().contains_key(&shown_entity));
+
+    let stats = client_app.world().resource::<ClientReplicationStats>();
+    assert_eq!(
+        stats.despawns, 0,
+        "resync shouldn't include visibility changes from before the request"
+    );
+}
+
+#[test]
+fn hide_and_request() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::Manual,
+                visibility_policy: VisibilityPolicy::Whitelist,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(server_entity, true);
+
+    server_app
+        .world_mut()
+        .resource_mut::<ServerTick>()
+        .increment();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(entity_map.to_client().contains_key(&server_entity));
+
+    client_app.world_mut().send_event(ResyncRequest);
+
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    // Hide the entity in the same tick the request is received.
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .visibility_mut()
+        .set_visibility(server_entity, false);
+
+    server_app.update();
+
+    // Send the resync only on the next tick.
+    server_app
+        .world_mut()
+        .resource_mut::<ServerTick>()
+        .increment();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(
+        !entity_map.to_client().contains_key(&server_entity),
+        "hidden entity should be despawned after resync"
+    );
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
@@ -0,0 +1,336 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn scoped() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    let client = client_app1.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .add_scope(ReplicationScope(1));
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, ReplicationScope(1)));
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, ReplicationScope(2)));
+    server_app.world_mut().spawn((Replicated, DummyComponent));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app1.update();
+    client_app2.update();
+
+    let mut replicated = client_app1.world_mut().query::<&Replicated>();
+    assert_eq!(
+        replicated.iter(client_app1.world()).count(),
+        2,
+        "client should receive entities from its scope and global entities"
+    );
+
+    let mut replicated = client_app2.world_mut().query::<&Replicated>();
+    assert_eq!(
+        replicated.iter(client_app2.world()).count(),
+        1,
+        "client without scopes should receive only global entities"
+    );
+}
+
+#[test]
+fn add_scope() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, ReplicationScope(1)));
+    server_app
+        .world_mut()
+        .spawn((Replicated, ReplicationScope(1)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 0);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .add_scope(ReplicationScope(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        2,
+        "entities from the added scope should be sent even without components"
+    );
+
+    let mut components = client_app.world_mut().query::<&DummyComponent>();
+    assert_eq!(components.iter(client_app.world()).count(), 1);
+}
+
+#[test]
+fn remove_scope() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .add_scope(ReplicationScope(1));
+
+    server_app
+        .world_mut()
+        .spawn((Replicated, ReplicationScope(1)));
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 2);
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id)
+        .remove_scope(ReplicationScope(1));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        1,
+        "entity from the removed scope should be despawned"
+    );
+}
+
+#[test]
+fn change_scope() {
+    let mut server_app = App::new();
+    let mut client_app1 = App::new();
+    let mut client_app2 = App::new();
+    for app in [&mut server_app, &mut client_app1, &mut client_app2] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app1);
+    server_app.connect_client(&mut client_app2);
+
+    let client1 = client_app1.world().resource::<RepliconClient>();
+    let client_id1 = client1.id().unwrap();
+    let client2 = client_app2.world().resource::<RepliconClient>();
+    let client_id2 = client2.id().unwrap();
+
+    let mut replicated_clients = server_app.world_mut().resource_mut::<ReplicatedClients>();
+    replicated_clients
+        .client_mut(client_id1)
+        .add_scope(ReplicationScope(1));
+    replicated_clients
+        .client_mut(client_id2)
+        .add_scope(ReplicationScope(2));
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, ReplicationScope(1)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app1.update();
+    client_app2.update();
+
+    let mut replicated1 = client_app1.world_mut().query::<&Replicated>();
+    assert_eq!(replicated1.iter(client_app1.world()).count(), 1);
+    let mut replicated2 = client_app2.world_mut().query::<&Replicated>();
+    assert_eq!(replicated2.iter(client_app2.world()).count(), 0);
+
+    server_app
+        .world_mut()
+        .entity_mut(server_entity)
+        .insert(ReplicationScope(2));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app1);
+    server_app.exchange_with_client(&mut client_app2);
+    client_app1.update();
+    client_app2.update();
+
+    assert_eq!(
+        replicated1.iter(client_app1.world()).count(),
+        0,
+        "entity should be despawned for the client that lost the scope"
+    );
+
+    let mut components = client_app2
+        .world_mut()
+        .query_filtered::<&DummyComponent, With<Replicated>>();
+    assert_eq!(
+        components.iter(client_app2.world()).count(),
+        1,
+        "entity should be sent as new for the client that gained the scope"
+    );
+}
+
+#[test]
+fn despawn_out_of_scope() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, ReplicationScope(1)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    server_app.world_mut().despawn(server_entity);
+
+    server_app.update();
+
+    let mut server = server_app.world_mut().resource_mut::<RepliconServer>();
+    assert_eq!(
+        server.drain_sent().count(),
+        0,
+        "despawn shouldn't be sent for an entity that the client never received"
+    );
+}
+
+#[test]
+fn mapping_out_of_scope() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+    client_app.insert_resource(UnmappedEntityPolicy::Keep);
+
+    server_app.connect_client(&mut client_app);
+
+    let client_entity = client_app.world_mut().spawn(PendingMapping::default()).id();
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent, ReplicationScope(1)))
+        .id();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let mut entity_map = server_app.world_mut().resource_mut::<ClientEntityMap>();
+    entity_map.insert(
+        client_id,
+        ClientMapping {
+            server_entity,
+            client_entity,
+        },
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert!(
+        entity_map.to_client().is_empty(),
+        "mapping for an entity outside of the client's scopes shouldn't be applied"
+    );
+
+    let outcomes: Vec<_> = client_app
+        .world_mut()
+        .resource_mut::<Events<MappingOutcome>>()
+        .drain()
+        .collect();
+    assert_eq!(outcomes, [MappingOutcome::Dropped { client_entity }]);
+}
+
+#[derive(Component, Deserialize, Serialize)]
+struct DummyComponent;
@@ -0,0 +1,124 @@
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use bevy_replicon::{
+    client::server_clock::ClientTick, prelude::*, server::server_tick::ServerTick,
+    test_app::ServerTestAppExt,
+};
+
+#[test]
+fn estimation() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    setup_clock(&mut client_app, None);
+
+    server_app.connect_client(&mut client_app);
+
+    assert!(!client_app.world().resource::<ServerClock>().is_synced());
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let clock = client_app.world().resource::<ServerClock>();
+    assert!(clock.is_synced());
+    assert_eq!(clock.tick(), server_tick, "should snap to the first tick");
+
+    client_app.update();
+    client_app.update();
+
+    let clock = client_app.world().resource::<ServerClock>();
+    assert_eq!(
+        clock.tick(),
+        server_tick + 2,
+        "should advance without received ticks"
+    );
+}
+
+#[test]
+fn latency() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    setup_clock(&mut client_app, None);
+
+    server_app.connect_client(&mut client_app);
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    client.set_rtt(0.4); // 2 ticks of one-way latency.
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let server_tick = **server_app.world().resource::<ServerTick>();
+    let clock = client_app.world().resource::<ServerClock>();
+    assert_eq!(clock.tick(), server_tick + 2);
+}
+
+#[test]
+fn fixed_lead() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    setup_clock(&mut client_app, Some(2));
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn(Replicated);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let clock = client_app.world().resource::<ServerClock>();
+    let target = clock.tick() + 2;
+    let client_tick = **client_app.world().resource::<ClientTick>();
+    assert_eq!(
+        client_tick,
+        target + 1,
+        "should snap to the target tick and then run the fixed schedule once"
+    );
+
+    let fixed_time = client_app.world().resource::<Time<Fixed>>();
+    assert!(fixed_time.timestep() <= Duration::from_millis(100));
+}
+
+fn setup_clock(app: &mut App, fixed_lead: Option<i32>) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )))
+    .add_plugins(ServerClockPlugin {
+        tick_duration: Duration::from_millis(100),
+        fixed_lead,
+        ..Default::default()
+    });
+}
@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+use bevy_replicon::{
+    core::server_entity_map::ServerEntityMap, prelude::*, test_app::ServerTestAppExt,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_trigger::<DummyEvent>(ChannelKind::Ordered);
+    }
+    client_app.init_resource::<TriggerReader>().add_observer(
+        |trigger: Trigger<DummyEvent>, mut reader: ResMut<TriggerReader>| {
+            reader.entities.push(trigger.entity());
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().commands().server_trigger(ToClients {
+        mode: SendMode::Broadcast,
+        event: DummyEvent,
+    });
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let reader = client_app.world().resource::<TriggerReader>();
+    assert_eq!(reader.entities, [Entity::PLACEHOLDER]);
+}
+
+#[test]
+fn mapping_and_sending_receiving() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_trigger::<DummyEvent>(ChannelKind::Ordered);
+    }
+    client_app.init_resource::<TriggerReader>().add_observer(
+        |trigger: Trigger<DummyEvent>, mut reader: ResMut<TriggerReader>| {
+            reader.entities.push(trigger.entity());
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn_empty().id();
+    let client_entity = client_app.world_mut().spawn_empty().id();
+    client_app
+        .world_mut()
+        .resource_mut::<ServerEntityMap>()
+        .insert(server_entity, client_entity);
+
+    server_app.world_mut().commands().server_trigger_targets(
+        ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent,
+        },
+        server_entity,
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let reader = client_app.world().resource::<TriggerReader>();
+    assert_eq!(reader.entities, [client_entity]);
+}
+
+#[test]
+fn unmapped_discarded() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .add_server_trigger::<DummyEvent>(ChannelKind::Ordered);
+    }
+    client_app.init_resource::<TriggerReader>().add_observer(
+        |trigger: Trigger<DummyEvent>, mut reader: ResMut<TriggerReader>| {
+            reader.entities.push(trigger.entity());
+        },
+    );
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn_empty().id();
+    server_app.world_mut().commands().server_trigger_targets(
+        ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent,
+        },
+        server_entity,
+    );
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let reader = client_app.world().resource::<TriggerReader>();
+    assert!(reader.entities.is_empty());
+}
+
+#[test]
+fn local_resending() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ))
+    .add_server_trigger::<DummyEvent>(ChannelKind::Ordered)
+    .init_resource::<TriggerReader>()
+    .add_observer(
+        |trigger: Trigger<DummyEvent>, mut reader: ResMut<TriggerReader>| {
+            reader.entities.push(trigger.entity());
+        },
+    );
+
+    let entity = app.world_mut().spawn_empty().id();
+    app.world_mut().commands().server_trigger_targets(
+        ToClients {
+            mode: SendMode::Broadcast,
+            event: DummyEvent,
+        },
+        entity,
+    );
+
+    app.update();
+    app.update();
+
+    let reader = app.world().resource::<TriggerReader>();
+    assert_eq!(reader.entities, [entity]);
+}
+
+#[derive(Event, Deserialize, Serialize)]
+struct DummyEvent;
+
+#[derive(Resource, Default)]
+struct TriggerReader {
+    entities: Vec<Entity>,
+}
@@ -1,6 +1,15 @@
-use bevy::prelude::*;
+use std::sync::OnceLock;
+
+use bevy::{ecs::entity::MapEntities, prelude::*};
 use bevy_replicon::{
-    client::confirm_history::ConfirmHistory, core::server_entity_map::ServerEntityMap, prelude::*,
+    client::{
+        confirm_history::ConfirmHistory, BufferedUpdates, ReplicationBudget, ServerUpdateTick,
+    },
+    core::{
+        replication::replication_registry::{ctx::SpawnCtx, ReplicationRegistry},
+        server_entity_map::ServerEntityMap,
+    },
+    prelude::*,
     test_app::ServerTestAppExt,
 };
 use serde::{Deserialize, Serialize};
@@ -45,6 +54,235 @@ fn empty() {
     );
 }
 
+#[test]
+fn custom_spawn() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    let mut registry = client_app.world_mut().resource_mut::<ReplicationRegistry>();
+    registry.spawn = spawn_marked;
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app.world_mut().spawn(Replicated).id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let (client_entity, spawned) = client_app
+        .world_mut()
+        .query_filtered::<(Entity, &Spawned), With<Replicated>>()
+        .single(client_app.world());
+    assert_eq!(spawned.0, server_entity);
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_entity),
+        Some(&client_entity)
+    );
+}
+
+#[test]
+fn custom_spawn_already_mapped() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    let mut registry = client_app.world_mut().resource_mut::<ReplicationRegistry>();
+    registry.spawn = spawn_reused;
+
+    server_app.connect_client(&mut client_app);
+
+    server_app.world_mut().spawn_batch([Replicated, Replicated]);
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app
+        .world_mut()
+        .query_filtered::<Entity, With<Replicated>>();
+    assert_eq!(
+        replicated.iter(client_app.world()).count(),
+        2,
+        "already mapped entity shouldn't be reused"
+    );
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(entity_map.to_client().len(), 2);
+    assert_eq!(entity_map.to_server().len(), 2);
+}
+
+#[test]
+fn custom_spawn_mapped() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate_mapped::<MappedComponent>();
+    }
+    let mut registry = client_app.world_mut().resource_mut::<ReplicationRegistry>();
+    registry.spawn = spawn_marked;
+
+    server_app.connect_client(&mut client_app);
+
+    let server_map_entity = server_app.world_mut().spawn_empty().id();
+    server_app
+        .world_mut()
+        .spawn((Replicated, MappedComponent(server_map_entity)));
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mapped_component = client_app
+        .world_mut()
+        .query::<&MappedComponent>()
+        .single(client_app.world());
+    let client_map_entity = mapped_component.0;
+
+    let spawned = client_app
+        .world()
+        .get::<Spawned>(client_map_entity)
+        .expect("mapped entity should be spawned with the custom function");
+    assert_eq!(spawned.0, server_map_entity);
+    assert!(client_app
+        .world()
+        .entity(client_map_entity)
+        .contains::<Replicated>());
+
+    let entity_map = client_app.world().resource::<ServerEntityMap>();
+    assert_eq!(
+        entity_map.to_client().get(&server_map_entity),
+        Some(&client_map_entity)
+    );
+}
+
+#[test]
+fn budget() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ));
+    }
+    client_app.insert_resource(ReplicationBudget {
+        entities: Some(1),
+        ..Default::default()
+    });
+
+    server_app.connect_client(&mut client_app);
+
+    // Spawn in different ticks to produce separate update messages.
+    server_app.world_mut().spawn(Replicated);
+    server_app.update();
+    server_app.world_mut().spawn(Replicated);
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 1);
+    assert_eq!(client_app.world().resource::<BufferedUpdates>().len(), 1);
+
+    client_app.update();
+
+    assert_eq!(replicated.iter(client_app.world()).count(), 2);
+    assert!(client_app.world().resource::<BufferedUpdates>().is_empty());
+}
+
+#[test]
+fn paused() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<BoolComponent>();
+    }
+    client_app.insert_resource(ReplicationPaused(true));
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, BoolComponent(false)))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<BoolComponent>(server_entity)
+        .unwrap()
+        .0 = true;
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let mut client = client_app.world_mut().resource_mut::<RepliconClient>();
+    assert_eq!(
+        client.drain_sent().count(),
+        1,
+        "mutation should be acknowledged"
+    );
+
+    let mut replicated = client_app.world_mut().query::<&Replicated>();
+    assert_eq!(replicated.iter(client_app.world()).count(), 0);
+    assert_eq!(
+        **client_app.world().resource::<ServerUpdateTick>(),
+        Default::default()
+    );
+
+    client_app.insert_resource(ReplicationPaused(false));
+    client_app.update();
+
+    let component = client_app
+        .world_mut()
+        .query::<&BoolComponent>()
+        .single(client_app.world());
+    assert!(
+        component.0,
+        "mutation should be applied after the insertion"
+    );
+}
+
 #[test]
 fn with_component() {
     let mut server_app = App::new();
@@ -259,3 +497,29 @@ fn after_despawn() {
 
 #[derive(Component, Deserialize, Serialize)]
 struct DummyComponent;
+
+#[derive(Component, Deserialize, Serialize)]
+struct BoolComponent(bool);
+
+/// Stores server entity from the spawn context.
+#[derive(Component)]
+struct Spawned(Entity);
+
+#[derive(Component, Deserialize, Serialize)]
+struct MappedComponent(Entity);
+
+impl MapEntities for MappedComponent {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0);
+    }
+}
+
+fn spawn_marked(ctx: &SpawnCtx, commands: &mut Commands) -> Entity {
+    commands.spawn(Spawned(ctx.server_entity)).id()
+}
+
+/// Always returns the first spawned entity, simulating a broken pool.
+fn spawn_reused(ctx: &SpawnCtx, commands: &mut Commands) -> Entity {
+    static POOLED: OnceLock<Entity> = OnceLock::new();
+    *POOLED.get_or_init(|| commands.spawn(Spawned(ctx.server_entity)).id())
+}
@@ -60,7 +60,71 @@ fn client_stats() {
     assert_eq!(stats.mappings, 1);
     assert_eq!(stats.despawns, 1);
     assert_eq!(stats.messages, 2);
-    assert_eq!(stats.bytes, 25);
+    assert_eq!(stats.bytes, 16);
+}
+
+#[test]
+fn server_stats() {
+    let mut server_app = App::new();
+    let mut client_app = App::new();
+    for app in [&mut server_app, &mut client_app] {
+        app.add_plugins((
+            MinimalPlugins,
+            RepliconPlugins.set(ServerPlugin {
+                tick_policy: TickPolicy::EveryFrame,
+                ..Default::default()
+            }),
+        ))
+        .replicate::<DummyComponent>();
+    }
+
+    server_app.connect_client(&mut client_app);
+
+    let server_entity = server_app
+        .world_mut()
+        .spawn((Replicated, DummyComponent))
+        .id();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+    server_app.exchange_with_client(&mut client_app);
+
+    server_app
+        .world_mut()
+        .get_mut::<DummyComponent>(server_entity)
+        .unwrap()
+        .set_changed();
+
+    server_app.update();
+    server_app.exchange_with_client(&mut client_app);
+    client_app.update();
+
+    let client = client_app.world().resource::<RepliconClient>();
+    let client_id = client.id().unwrap();
+
+    let stats = server_app.world().resource::<ServerReplicationStats>();
+    let client_stats = *stats.get(client_id).unwrap();
+    assert_eq!(client_stats.entities, 2);
+    assert_eq!(client_stats.components, 2);
+    assert_eq!(client_stats.update_messages, 1);
+    assert_eq!(client_stats.update_bytes, 5);
+    assert_eq!(client_stats.mutate_messages, 1);
+    assert_eq!(client_stats.mutate_bytes, 6);
+    assert_eq!(client_stats.pending_mutations, 1);
+    assert_eq!(client_stats.timed_out_acks, 0);
+    assert_eq!(stats.total(), client_stats);
+
+    let client_stats = *client_app.world().resource::<ClientReplicationStats>();
+    assert_eq!(
+        client_stats.bytes, client_stats.bytes,
+        "server should count the same payload"
+    );
+
+    server_app.disconnect_client(&mut client_app);
+
+    let stats = server_app.world().resource::<ServerReplicationStats>();
+    assert!(stats.get(client_id).is_none());
 }
 
 #[derive(Component, Deserialize, Serialize)]